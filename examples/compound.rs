@@ -0,0 +1,118 @@
+/// This example shows compound bodies made of several shapes.
+/// A T-handle spinning around its intermediate axis flips over like in the Dzhanibekov effect,
+/// and a dumbbell spins steadily around its long axis.
+mod utils;
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+use pdrust::{
+    body::{bundle::RigidBodyBundle, RigidBody},
+    math::{to_f32, Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+};
+use utils::ExamplesUtilsPlugin;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(pdrust::PDRustPlugin)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_plugins(ExamplesUtilsPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut settings: ResMut<SettingsResource>,
+) {
+    settings.gravity_vector = Vector::ZERO;
+    settings.method = IntergrationMethod::SemiImplicitEuler;
+    settings.implicit_gyroscopic_torque = true;
+
+    let cylinder = |mass: Real, r: Real, height: Real, transform: Transform| {
+        (
+            Mesh::from(shape::Cylinder {
+                radius: to_f32(r),
+                height: to_f32(height),
+                ..default()
+            }),
+            RigidBody::new_cylinder(mass, r, height, Vector::ZERO, Vector::ZERO).into(),
+            transform,
+        )
+    };
+    let sphere = |mass: Real, r: Real, transform: Transform| {
+        (
+            Mesh::from(shape::UVSphere {
+                radius: to_f32(r),
+                ..default()
+            }),
+            RigidBody::new_sphere(mass, r, Vector::ZERO, Vector::ZERO).into(),
+            transform,
+        )
+    };
+
+    // The handle lies along X on top of the shaft along Y. The intermediate axis is X.
+    RigidBodyBundle::spawn_new_compound(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::GOLD.into()),
+        vec![
+            cylinder(
+                1.0,
+                0.1,
+                2.0,
+                Transform::from_xyz(0.0, 1.0, 0.0)
+                    .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+            ),
+            cylinder(1.0, 0.1, 2.0, Transform::from_xyz(0.0, 0.0, 0.0)),
+        ],
+        Transform::from_xyz(-3.0, 0.0, 0.0),
+        Vector::ZERO,
+        Vector::new(2.0, 0.001, 0.0),
+    );
+
+    RigidBodyBundle::spawn_new_compound(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::CYAN.into()),
+        vec![
+            sphere(2.0, 0.4, Transform::from_xyz(-1.0, 0.0, 0.0)),
+            sphere(2.0, 0.4, Transform::from_xyz(1.0, 0.0, 0.0)),
+            cylinder(
+                0.5,
+                0.1,
+                2.0,
+                Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),
+            ),
+        ],
+        Transform::from_xyz(3.0, 0.0, 0.0),
+        Vector::ZERO,
+        Vector::new(0.5, 0.0, 0.0),
+    );
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::WHITE,
+            illuminance: 10_000.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 5.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        PanOrbitCamera {
+            focus: Vec3::ZERO,
+            ..default()
+        },
+    ));
+}
@@ -0,0 +1,137 @@
+/// This example shows how to plug your own integration method into the simulation.
+/// A spring pendulum is integrated with the explicit midpoint method.
+mod utils;
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+use pdrust::{
+    body::{bundle::RigidBodyBundle, Body, BodyType},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    solver::{
+        exponential_map, rotate_around_center_of_mass, state::PhysicsState, SimulationSolver,
+    },
+    springs::bundle::SpringBundle,
+};
+use utils::ExamplesUtilsPlugin;
+
+/// Explicit midpoint method: derivatives are evaluated in the middle of the step.
+struct MidpointSolver;
+
+impl SimulationSolver for MidpointSolver {
+    fn step(&self, state: &mut PhysicsState, dt: Real) {
+        let initial = state.bodies.clone();
+
+        // Half step with forces at the beginning of the step
+        for body_state in state.bodies.iter_mut() {
+            let Some(body) = body_state.rigid_body.as_mut() else {
+                continue;
+            };
+            let transform = &mut body_state.transform;
+            let omega = body.get_angular_velocity(transform);
+            transform.translation += body.get_velocity() * dt / 2.0;
+            let rotation = exponential_map(omega, dt / 2.0) * transform.rotation;
+            rotate_around_center_of_mass(body, transform, rotation);
+            body.pulse += body.force * dt / 2.0;
+            body.angular_momentum += body.torque * dt / 2.0;
+        }
+
+        // Full step with derivatives in the middle of the step
+        state.accumulate_forces();
+        for (body_state, initial) in state.bodies.iter_mut().zip(initial) {
+            let (Some(body), Some(initial_body)) =
+                (body_state.rigid_body.as_mut(), initial.rigid_body)
+            else {
+                continue;
+            };
+            let omega = body.get_angular_velocity(&body_state.transform);
+            let velocity = body.get_velocity();
+            let transform = &mut body_state.transform;
+            *transform = initial.transform;
+            transform.translation += velocity * dt;
+            let rotation = exponential_map(omega, dt) * transform.rotation;
+            rotate_around_center_of_mass(body, transform, rotation);
+            body.pulse = initial_body.pulse + body.force * dt;
+            body.angular_momentum = initial_body.angular_momentum + body.torque * dt;
+        }
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(pdrust::PDRustPlugin)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_plugins(ExamplesUtilsPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut settings: ResMut<SettingsResource>,
+) {
+    settings.method = IntergrationMethod::Custom(Box::new(MidpointSolver));
+
+    let bob = RigidBodyBundle::spawn_new_box(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::RED.into()),
+        1.0,
+        1.0,
+        1.0,
+        1.0,
+        Transform::from_xyz(5.0, 5.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
+    );
+
+    let anchor = commands
+        .spawn((Transform::from_xyz(0.0, 5.0, 0.0), Body, BodyType::Static))
+        .id();
+
+    commands.spawn(SpringBundle::new(
+        bob,
+        Vector::new(0.0, 0.5, 0.0),
+        anchor,
+        Vector::ZERO,
+        5.0,
+        20.0,
+        0.0,
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cylinder {
+                radius: 0.1,
+                height: 1.0,
+                ..Default::default()
+            })),
+            material: materials.add(Color::BLUE.into()),
+            ..default()
+        },
+    ));
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::WHITE,
+            illuminance: 10_000.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 20.0, 15.0)
+                .looking_at(Vec3::from_array([0.0, 10.0, 0.0]), Vec3::Y),
+            ..default()
+        },
+        PanOrbitCamera {
+            focus: Vec3::from_array([0.0, 0.0, 0.0]),
+            ..default()
+        },
+    ));
+}
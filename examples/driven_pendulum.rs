@@ -0,0 +1,130 @@
+/// This example shows a kinematic body: the pivot of a pendulum is driven back and forth
+/// along a prescribed path, and the pendulum is pushed by its real velocity.
+mod utils;
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+use pdrust::{
+    body::{bundle::RigidBodyBundle, Body, BodyType, PhysicsTransform},
+    clock::{advance_simulation_clock, SimulationClock},
+    constraint::distance::bundle::DistanceConstraintBundle,
+    control::SimulationSet,
+    math::{Real, Vector},
+    settings::SettingsResource,
+    solver::step_in_simulation,
+};
+use utils::ExamplesUtilsPlugin;
+
+/// Amplitude of the pivot motion
+const AMPLITUDE: Real = 1.0;
+/// Angular frequency of the pivot motion
+const FREQUENCY: Real = 2.0;
+
+/// A marker of the driven pivot
+#[derive(Component)]
+struct Pivot;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(pdrust::PDRustPlugin)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_plugins(ExamplesUtilsPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(
+            FixedUpdate,
+            drive_pivot
+                .after(advance_simulation_clock)
+                .before(step_in_simulation)
+                .in_set(SimulationSet),
+        )
+        .run();
+}
+
+/// Sets velocity of the pivot so that it reaches its place on the path at the end of the step.
+fn drive_pivot(
+    mut pivots: Query<(&PhysicsTransform, &mut BodyType), With<Pivot>>,
+    clock: Res<SimulationClock>,
+) {
+    let time = clock.elapsed_seconds() as Real;
+    for (transform, mut body_type) in pivots.iter_mut() {
+        let target = PhysicsTransform::from_xyz(AMPLITUDE * (FREQUENCY * time).sin(), 0.0, 0.0);
+        *body_type = BodyType::kinematic_towards(transform, &target, clock.delta_seconds());
+    }
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut settings: ResMut<SettingsResource>,
+) {
+    settings.constraints_substeps = 32;
+    settings.integration_substeps = 32;
+
+    let length = 3.0;
+
+    let pivot = commands
+        .spawn((
+            Body,
+            BodyType::Kinematic {
+                velocity: Vector::ZERO,
+                angular_velocity: Vector::ZERO,
+            },
+            Pivot,
+            PhysicsTransform::IDENTITY,
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(0.4, 0.2, 0.4))),
+                material: materials.add(Color::RED.into()),
+                ..default()
+            },
+        ))
+        .id();
+
+    let bob = RigidBodyBundle::spawn_new_sphere(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::GOLD.into()),
+        1.0,
+        0.3,
+        Transform::from_xyz(0.0, -3.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
+    );
+
+    DistanceConstraintBundle::spawn_new(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::AZURE.into()),
+        pivot,
+        bob,
+        Vector::ZERO,
+        Vector::ZERO,
+        length,
+        length,
+    );
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::WHITE,
+            illuminance: 10_000.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, -1.5, 10.0)
+                .looking_at(Vec3::new(0.0, -1.5, 0.0), Vec3::Y),
+            ..default()
+        },
+        PanOrbitCamera {
+            focus: Vec3::new(0.0, -1.5, 0.0),
+            ..default()
+        },
+    ));
+}
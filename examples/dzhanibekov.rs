@@ -0,0 +1,71 @@
+/// This example shows the Dzhanibekov effect (tennis racket theorem).
+/// A box spinning around its intermediate axis periodically flips over.
+/// Rotation is integrated with an implicit gyroscopic term, so the flips stay regular.
+mod utils;
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+use pdrust::{
+    body::bundle::RigidBodyBundle,
+    math::Vector,
+    settings::{IntergrationMethod, SettingsResource},
+};
+use utils::ExamplesUtilsPlugin;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(pdrust::PDRustPlugin)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_plugins(ExamplesUtilsPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut settings: ResMut<SettingsResource>,
+) {
+    settings.gravity_vector = Vector::ZERO;
+    settings.method = IntergrationMethod::SemiImplicitEuler;
+    settings.implicit_gyroscopic_torque = true;
+
+    // Intermediate axis is Y. A tiny spin around X makes the rotation unstable.
+    RigidBodyBundle::spawn_new_box(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::GOLD.into()),
+        1.0,
+        3.0,
+        1.5,
+        0.5,
+        Transform::from_xyz(0.0, 0.0, 0.0),
+        Vector::ZERO,
+        Vector::new(0.001, 4.0, 0.0),
+    );
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::WHITE,
+            illuminance: 10_000.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 5.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        PanOrbitCamera {
+            focus: Vec3::ZERO,
+            ..default()
+        },
+    ));
+}
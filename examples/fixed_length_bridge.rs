@@ -2,8 +2,9 @@ mod utils;
 use bevy::prelude::*;
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 use pdrust::{
-    body::{bundle::RigidBodyBundle, Body},
+    body::{bundle::RigidBodyBundle, Body, BodyType, RigidBody},
     constraint::distance::bundle::DistanceConstraintBundle,
+    math::{to_f32, to_vec3, Real, Vector},
     settings::SettingsResource,
 };
 use utils::ExamplesUtilsPlugin;
@@ -26,8 +27,10 @@ fn setup(
     mut settings: ResMut<SettingsResource>,
 ) {
     settings.print_energy_in_console = true;
+    // The bridge settles down thanks to damping and then falls asleep
+    settings.allow_sleeping = true;
 
-    let start = Vec3::new(-5.0, 0.0, 0.0);
+    let start = Vector::new(-5.0, 0.0, 0.0);
     let cube_size = 0.2;
     let n = 50;
     let constrait_size = 1.0;
@@ -38,13 +41,14 @@ fn setup(
         commands
             .spawn((
                 Body,
+                BodyType::Static,
                 PbrBundle {
                     mesh: meshes.add(Mesh::from(shape::UVSphere {
                         radius: 0.1,
                         ..default()
                     })),
                     material: materials.add(Color::RED.into()),
-                    transform: Transform::from_translation(start),
+                    transform: Transform::from_translation(to_vec3(start)),
                     ..default()
                 },
             ))
@@ -53,35 +57,45 @@ fn setup(
 
     for i in 0..n {
         let mut pos = start;
-        pos.x += constrait_size * (i as f32 + 1.0) + cube_size * i as f32;
+        pos.x += constrait_size * (i as Real + 1.0) + cube_size * i as Real;
 
-        bodies.push(RigidBodyBundle::spawn_new_box(
+        let cube = RigidBodyBundle::spawn(
             &mut commands,
             &mut meshes,
             materials.add(Color::GREEN.into()),
-            1.0,
-            cube_size,
-            cube_size,
-            cube_size,
-            Transform::from_translation(pos),
-            Vec3::ZERO,
-            Vec3::ZERO,
-        ))
+            Mesh::from(shape::Box::new(
+                to_f32(cube_size),
+                to_f32(cube_size),
+                to_f32(cube_size),
+            )),
+            RigidBody::new_box(
+                1.0,
+                cube_size,
+                cube_size,
+                cube_size,
+                Vector::ZERO,
+                Vector::ZERO,
+            )
+            .with_damping(1.0, 0.1),
+            Transform::from_translation(to_vec3(pos)),
+        );
+        bodies.push(cube);
     }
 
     let mut final_pos = start;
-    final_pos.x += constrait_size * (n as f32 + 1.0) + cube_size * n as f32;
+    final_pos.x += constrait_size * (n as Real + 1.0) + cube_size * n as Real;
     bodies.push(
         commands
             .spawn((
                 Body,
+                BodyType::Static,
                 PbrBundle {
                     mesh: meshes.add(Mesh::from(shape::UVSphere {
                         radius: 0.2,
                         ..default()
                     })),
                     material: materials.add(Color::RED.into()),
-                    transform: Transform::from_translation(final_pos),
+                    transform: Transform::from_translation(to_vec3(final_pos)),
                     ..default()
                 },
             ))
@@ -95,8 +109,8 @@ fn setup(
             materials.add(Color::AZURE.into()),
             bodies[i],
             bodies[i + 1],
-            Vec3::ZERO,
-            Vec3::ZERO,
+            Vector::ZERO,
+            Vector::ZERO,
             constrait_size,
             constrait_size,
         );
@@ -3,8 +3,9 @@ mod utils;
 use bevy::prelude::*;
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 use pdrust::{
-    body::{bundle::RigidBodyBundle, Body},
+    body::{bundle::RigidBodyBundle, Body, BodyType},
     constraint::distance::bundle::DistanceConstraintBundle,
+    math::{to_f32, Real, Vector},
     settings::SettingsResource,
 };
 use utils::ExamplesUtilsPlugin;
@@ -30,9 +31,10 @@ fn setup(
     settings.baumgarte_constant = 0.01;
     settings.integration_substeps = 32;
     settings.print_energy_in_console = true;
+    settings.energy_projection = true;
 
-    let l1 = 5.0;
-    let l2 = 5.0;
+    let l1: Real = 5.0;
+    let l2: Real = 5.0;
 
     let b1 = RigidBodyBundle::spawn_new_box(
         &mut commands,
@@ -42,9 +44,9 @@ fn setup(
         1.0,
         1.0,
         1.0,
-        Transform::from_xyz(l1, 0.0, 0.0),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Transform::from_xyz(to_f32(l1), 0.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let b2 = RigidBodyBundle::spawn_new_box(
@@ -55,14 +57,15 @@ fn setup(
         1.0,
         1.0,
         1.0,
-        Transform::from_xyz(l1 + l2, 0.0, 0.0),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Transform::from_xyz(to_f32(l1 + l2), 0.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let anchor = commands
         .spawn((
             Body,
+            BodyType::Static,
             PbrBundle {
                 mesh: meshes.add(Mesh::from(shape::UVSphere {
                     radius: 0.1,
@@ -92,8 +95,8 @@ fn setup(
         materials.add(Color::AZURE.into()),
         anchor,
         b1,
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
         l1,
         l1,
     );
@@ -104,8 +107,8 @@ fn setup(
         materials.add(Color::AZURE.into()),
         b1,
         b2,
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
         l2,
         l2,
     );
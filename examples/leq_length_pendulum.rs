@@ -2,8 +2,9 @@ mod utils;
 use bevy::prelude::*;
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 use pdrust::{
-    body::{bundle::RigidBodyBundle, Body},
+    body::{bundle::RigidBodyBundle, Body, BodyType},
     constraint::distance::bundle::DistanceConstraintBundle,
+    math::{to_f32, Real, Vector},
     settings::SettingsResource,
 };
 use utils::ExamplesUtilsPlugin;
@@ -30,8 +31,8 @@ fn setup(
     settings.integration_substeps = 32;
     settings.baumgarte_constant = 0.05;
 
-    let l1 = 3.0;
-    let l2 = 2.0;
+    let l1: Real = 3.0;
+    let l2: Real = 2.0;
     let r1 = 0.25;
     let m1 = 1.0;
     let m2 = 1.0;
@@ -43,9 +44,9 @@ fn setup(
         materials.add(Color::RED.into()),
         m1,
         r1,
-        Transform::from_xyz(l1, 0.0, 0.0),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Transform::from_xyz(to_f32(l1), 0.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let b2 = RigidBodyBundle::spawn_new_sphere(
@@ -54,14 +55,15 @@ fn setup(
         materials.add(Color::RED.into()),
         m2,
         r2,
-        Transform::from_xyz(l1 + l2, 0.0, 0.0),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Transform::from_xyz(to_f32(l1 + l2), 0.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let anchor = commands
         .spawn((
             Body,
+            BodyType::Static,
             PbrBundle {
                 mesh: meshes.add(Mesh::from(shape::UVSphere {
                     radius: 0.1,
@@ -91,8 +93,8 @@ fn setup(
         materials.add(Color::AZURE.into()),
         anchor,
         b1,
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
         0.0,
         l1,
     );
@@ -103,8 +105,8 @@ fn setup(
         materials.add(Color::AZURE.into()),
         b1,
         b2,
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
         0.0,
         l2,
     );
@@ -0,0 +1,55 @@
+/// This example shows how precision of the simulation core affects long runs.
+/// A satellite far from the origin orbits a point under a central force applied every step,
+/// and the drift of its orbit radius is printed every 100 orbits.
+///
+/// Compare `cargo run --example orbit_drift` with
+/// `cargo run --example orbit_drift --features f64`.
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{consts::PI, Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    world::PhysicsWorld,
+};
+
+/// Position of the attracting point. Large coordinates make rounding errors noticeable.
+const CENTER: Vector = Vector::new(10_000.0, 0.0, 10_000.0);
+const RADIUS: Real = 10.0;
+/// Gravitational parameter of the attracting point
+const MU: Real = 1000.0;
+const STEPS_PER_ORBIT: usize = 600;
+const ORBITS: usize = 1000;
+
+fn main() {
+    println!(
+        "Simulation core precision: {}",
+        std::any::type_name::<Real>()
+    );
+
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::SemiImplicitEuler,
+        gravity_vector: Vector::ZERO,
+        integration_substeps: 1,
+        ..default()
+    });
+    let speed = (MU / RADIUS).sqrt();
+    let satellite = world.add_body(
+        PhysicsTransform::from_translation(CENTER + Vector::new(RADIUS, 0.0, 0.0)),
+        RigidBody::new_sphere(1.0, 0.1, Vector::new(0.0, 0.0, speed), Vector::ZERO),
+    );
+
+    let period = 2.0 * PI * RADIUS / speed;
+    let dt = period / STEPS_PER_ORBIT as Real;
+    for orbit in 1..=ORBITS {
+        for _ in 0..STEPS_PER_ORBIT {
+            let r = world.transform(satellite).translation - CENTER;
+            let force = -MU * r / r.length().powi(3);
+            world.set_external_force(satellite, force, Vector::ZERO);
+            world.step(dt);
+        }
+        if orbit % 100 == 0 {
+            let r = world.transform(satellite).translation - CENTER;
+            println!("Orbit {orbit:4}: radius drift {:+.3e}", r.length() - RADIUS);
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 use pdrust::{
-    body::{bundle::RigidBodyBundle},
+    body::bundle::RigidBodyBundle,
     constraint::pulley::bundle::PulleyBundle,
+    math::{to_vec3, Real, Vector},
     settings::SettingsResource,
 };
 mod utils;
@@ -34,17 +35,17 @@ fn setup(
     let m2 = m1;
     let m_central = m1;
 
-    let equilibrium_pos = Vec3::new(0.0, -half_l / f32::sqrt(3.0), 0.0);
-    let equilibrium_offset: f32 = -0.0;
-    let b_central_pos = equilibrium_pos + Vec3::new(0.0, equilibrium_offset, 0.0);
+    let equilibrium_pos = Vector::new(0.0, -half_l / Real::sqrt(3.0), 0.0);
+    let equilibrium_offset: Real = -0.0;
+    let b_central_pos = equilibrium_pos + Vector::new(0.0, equilibrium_offset, 0.0);
 
-    let pulley1_pos = Vec3::new(-half_l, 0.0, 0.0);
-    let pulley2_pos = Vec3::new(half_l, 0.0, 0.0);
+    let pulley1_pos = Vector::new(-half_l, 0.0, 0.0);
+    let pulley2_pos = Vector::new(half_l, 0.0, 0.0);
 
     let constraint_distance = 3.0 * half_l;
     let vertical_offset = constraint_distance - (b_central_pos - pulley1_pos).length();
-    let b1_pos = pulley1_pos + Vec3::new(0.0, -vertical_offset, 0.0);
-    let b2_pos = pulley2_pos + Vec3::new(0.0, -vertical_offset, 0.0);
+    let b1_pos = pulley1_pos + Vector::new(0.0, -vertical_offset, 0.0);
+    let b2_pos = pulley2_pos + Vector::new(0.0, -vertical_offset, 0.0);
 
     let b1 = RigidBodyBundle::spawn_new_box(
         &mut commands,
@@ -54,9 +55,9 @@ fn setup(
         1.0,
         1.0,
         1.0,
-        Transform::from_translation(b1_pos),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Transform::from_translation(to_vec3(b1_pos)),
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let b2 = RigidBodyBundle::spawn_new_box(
@@ -67,9 +68,9 @@ fn setup(
         1.0,
         1.0,
         1.0,
-        Transform::from_translation(b2_pos),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Transform::from_translation(to_vec3(b2_pos)),
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let central_body = RigidBodyBundle::spawn_new_sphere(
@@ -78,9 +79,9 @@ fn setup(
         materials.add(Color::GREEN.into()),
         m_central,
         0.5,
-        Transform::from_translation(b_central_pos),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Transform::from_translation(to_vec3(b_central_pos)),
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     PulleyBundle::spawn_new(
@@ -91,8 +92,8 @@ fn setup(
         materials.add(Color::BEIGE.into()),
         b1,
         central_body,
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
         constraint_distance,
         pulley1_pos,
     );
@@ -105,8 +106,8 @@ fn setup(
         materials.add(Color::BEIGE.into()),
         b2,
         central_body,
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
         constraint_distance,
         pulley2_pos,
     );
@@ -119,7 +120,7 @@ fn setup(
             ..default()
         })),
         material: materials.add(Color::CYAN.into()),
-        transform: Transform::from_translation(equilibrium_pos),
+        transform: Transform::from_translation(to_vec3(equilibrium_pos)),
         ..default()
     });
 
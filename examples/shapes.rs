@@ -0,0 +1,145 @@
+/// This example shows the library of shapes. Every body gets the same angular momentum,
+/// so bodies with smaller inertia spin faster.
+mod utils;
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+use pdrust::{body::bundle::RigidBodyBundle, math::Vector, settings::SettingsResource};
+use utils::ExamplesUtilsPlugin;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(pdrust::PDRustPlugin)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_plugins(ExamplesUtilsPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut settings: ResMut<SettingsResource>,
+) {
+    settings.gravity_vector = Vector::ZERO;
+    settings.implicit_gyroscopic_torque = true;
+
+    let mass = 1.0;
+    let angular_momentum = Vector::new(0.5, 0.0, 0.0);
+    let transform = |x: f32| Transform::from_xyz(x, 0.0, 0.0);
+
+    RigidBodyBundle::spawn_new_box(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::RED.into()),
+        mass,
+        1.0,
+        1.0,
+        1.0,
+        transform(-7.5),
+        Vector::ZERO,
+        angular_momentum,
+    );
+    RigidBodyBundle::spawn_new_sphere(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::ORANGE.into()),
+        mass,
+        0.5,
+        transform(-5.0),
+        Vector::ZERO,
+        angular_momentum,
+    );
+    RigidBodyBundle::spawn_new_hollow_sphere(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::GOLD.into()),
+        mass,
+        0.5,
+        transform(-2.5),
+        Vector::ZERO,
+        angular_momentum,
+    );
+    RigidBodyBundle::spawn_new_ellipsoid(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::GREEN.into()),
+        mass,
+        0.3,
+        0.8,
+        0.5,
+        transform(0.0),
+        Vector::ZERO,
+        angular_momentum,
+    );
+    RigidBodyBundle::spawn_new_cylinder(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::CYAN.into()),
+        mass,
+        0.4,
+        1.5,
+        transform(2.5),
+        Vector::ZERO,
+        angular_momentum,
+    );
+    RigidBodyBundle::spawn_new_capsule(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::BLUE.into()),
+        mass,
+        0.3,
+        1.0,
+        transform(5.0),
+        Vector::ZERO,
+        angular_momentum,
+    );
+    RigidBodyBundle::spawn_new_cone(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::PURPLE.into()),
+        mass,
+        0.5,
+        1.5,
+        transform(7.5),
+        Vector::ZERO,
+        angular_momentum,
+    );
+    RigidBodyBundle::spawn_new_rod(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::WHITE.into()),
+        mass,
+        2.0,
+        0.05,
+        transform(10.0),
+        Vector::ZERO,
+        angular_momentum,
+    );
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::WHITE,
+            illuminance: 10_000.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(1.25, 5.0, 15.0)
+                .looking_at(Vec3::new(1.25, 0.0, 0.0), Vec3::Y),
+            ..default()
+        },
+        PanOrbitCamera {
+            focus: Vec3::new(1.25, 0.0, 0.0),
+            ..default()
+        },
+    ));
+}
@@ -1,12 +1,11 @@
 /// This example shows a double pendulum made with springs.
 /// Try to change body attachment point to see what happens!
 mod utils;
-use bevy::{
-    prelude::*,
-};
+use bevy::prelude::*;
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
 use pdrust::{
-    body::{bundle::RigidBodyBundle, Body},
+    body::{bundle::RigidBodyBundle, Body, BodyType},
+    math::Vector,
     springs::bundle::SpringBundle,
 };
 use utils::ExamplesUtilsPlugin;
@@ -53,8 +52,8 @@ fn setup(
         1.0,
         1.0,
         Transform::from_xyz(5.0, 0.0, 5.0),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let b2 = RigidBodyBundle::spawn_new_box(
@@ -66,20 +65,20 @@ fn setup(
         1.0,
         2.0,
         Transform::from_xyz(5.0, 0.0, 0.0),
-        Vec3::ZERO,
-        Vec3::ZERO,
+        Vector::ZERO,
+        Vector::ZERO,
     );
 
     let anchor = commands
-        .spawn((Transform::from_xyz(0.0, 5.0, 0.0), Body))
+        .spawn((Transform::from_xyz(0.0, 5.0, 0.0), Body, BodyType::Static))
         .id();
 
     let _spring1 = commands
         .spawn(SpringBundle::new(
             b1,
-            Vec3::new(0.5, 0.5, 0.5),
+            Vector::new(0.5, 0.5, 0.5),
             b2,
-            Vec3::new(0.0, -0.5, 0.0),
+            Vector::new(0.0, -0.5, 0.0),
             5.0,
             5.0,
             1.0,
@@ -99,9 +98,9 @@ fn setup(
     let _spring2 = commands
         .spawn(SpringBundle::new(
             b2,
-            Vec3::new(0.0, 0.5, 0.0),
+            Vector::new(0.0, 0.5, 0.0),
             anchor,
-            Vec3::new(0.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 0.0),
             5.0,
             5.0,
             1.0,
@@ -1,6 +1,18 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
-use pdrust::{energy::Energy, settings::SettingsResource};
+use pdrust::{
+    body::RigidBody,
+    clock::SimulationClock,
+    control::{SimulationState, StepSimulation},
+    energy::{Energy, EnergyProjection},
+    history::{ScrubHistory, SimulationHistory},
+    math::Real,
+    settings::SettingsResource,
+    solver::IntegrationStats,
+};
+
+/// Number of steps recorded when history is turned on, a minute at the default timestep
+const HISTORY_CAPACITY: usize = 3840;
 
 pub struct ExamplesUtilsPlugin;
 
@@ -14,16 +26,55 @@ impl Plugin for ExamplesUtilsPlugin {
 fn simulation_settings_ui(
     mut contexts: EguiContexts,
     mut settings: ResMut<SettingsResource>,
+    stats: Res<IntegrationStats>,
+    clock: Res<SimulationClock>,
+    mut state: ResMut<SimulationState>,
+    mut step_requests: EventWriter<StepSimulation>,
+    mut history: ResMut<SimulationHistory>,
+    mut history_requests: EventWriter<ScrubHistory>,
     energy: Query<&Energy>,
+    bodies: Query<&RigidBody>,
+    energy_projection: Res<EnergyProjection>,
 ) {
     egui::Window::new("Simulation Settings").show(contexts.ctx_mut(), |ui| {
+        ui.horizontal(|ui| {
+            if state.is_running() {
+                if ui.button("Pause").clicked() {
+                    state.pause();
+                }
+            } else if ui.button("Resume").clicked() {
+                state.resume();
+            }
+            if ui.button("Step").clicked() {
+                step_requests.send(StepSimulation(1));
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Rewind").clicked() {
+                state.rewind();
+            }
+            if ui.button("Step back").clicked() {
+                history_requests.send(ScrubHistory(-1));
+            }
+            if ui.button("Step forward").clicked() {
+                history_requests.send(ScrubHistory(1));
+            }
+        });
+        let mut recording = history.capacity > 0;
+        if ui.checkbox(&mut recording, "Record history").changed() {
+            history.capacity = if recording { HISTORY_CAPACITY } else { 0 };
+        }
+        ui.add(egui::Label::new(match history.position() {
+            Some(position) => format!("History: step {} of {}", position + 1, history.len()),
+            None => format!("History: {} steps recorded", history.len()),
+        }));
         ui.add(
             egui::Slider::new(&mut settings.integration_substeps, 1..=32)
                 .text("Integration substeps"),
         );
         ui.add(
             egui::Slider::new(&mut settings.constraints_substeps, 1..=32)
-                .text("Constraints integration substeps"),
+                .text("Constraint iterations per substep"),
         );
         ui.add(
             egui::Slider::new(&mut settings.baumgarte_constant, 0.0..=0.1)
@@ -33,9 +84,36 @@ fn simulation_settings_ui(
             egui::Slider::new(&mut settings.slow_motion_koef, 1.0..=16.0)
                 .text("Slow Motion coefficient"),
         );
+        ui.checkbox(&mut settings.allow_sleeping, "Allow sleeping");
+        ui.checkbox(&mut settings.energy_projection, "Energy projection");
+        ui.add(egui::Label::new(format!(
+            "Simulated time: {:.2} s",
+            clock.elapsed_seconds()
+        )));
+        ui.add(egui::Label::new(format!(
+            "Integration steps: {}",
+            stats.steps
+        )));
+        ui.add(egui::Label::new(format!(
+            "Sleeping bodies: {}",
+            stats.sleeping_bodies
+        )));
+        let total_energy = energy.iter().map(|e| e.get_energy()).sum::<Real>();
         ui.add(egui::Label::new(format!(
-            "Sum of energies: {:.5}",
-            energy.iter().map(|e| e.get_energy()).sum::<f32>()
+            "Sum of energies: {total_energy:.5}"
         )));
+        let dissipated_energy = bodies.iter().map(|b| b.dissipated_energy).sum::<Real>();
+        if dissipated_energy != 0.0 {
+            ui.add(egui::Label::new(format!(
+                "Dissipated energy: {dissipated_energy:.5} (sum: {:.5})",
+                total_energy + dissipated_energy
+            )));
+        }
+        if let Some(correction) = energy_projection.correction() {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("Energy projection is active, last correction: {correction:+.5}"),
+            );
+        }
     });
 }
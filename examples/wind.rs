@@ -0,0 +1,111 @@
+/// This example shows aerodynamic drag: a sphere falls with its terminal velocity, and a
+/// pendulum leans in a wind that gets stronger with height.
+mod utils;
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+use pdrust::{
+    body::{bundle::RigidBodyBundle, drag::Drag, Body, BodyType, RigidBody},
+    constraint::distance::bundle::DistanceConstraintBundle,
+    math::Vector,
+    wind::Wind,
+};
+use utils::ExamplesUtilsPlugin;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(pdrust::PDRustPlugin)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_plugins(ExamplesUtilsPlugin)
+        .insert_resource(Wind::field(|point| {
+            Vector::new(0.5 * (6.0 + point.y).max(0.0), 0.0, 0.0)
+        }))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // A light sphere falling from high above
+    let (mass, radius) = (0.05, 0.3);
+    let sphere = RigidBodyBundle::spawn_new_sphere(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::GOLD.into()),
+        mass,
+        radius,
+        Transform::from_xyz(-3.0, 20.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
+    );
+    commands.entity(sphere).insert(
+        RigidBody::new_sphere(mass, radius, Vector::ZERO, Vector::ZERO)
+            .with_drag(Drag::new_sphere(0.47, radius)),
+    );
+
+    // A pendulum with a box bob
+    let pivot = commands
+        .spawn((
+            Body,
+            BodyType::Static,
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(0.4, 0.2, 0.4))),
+                material: materials.add(Color::RED.into()),
+                transform: Transform::from_xyz(1.0, 0.0, 0.0),
+                ..default()
+            },
+        ))
+        .id();
+    let (mass, size) = (0.2, 0.5);
+    let bob = RigidBodyBundle::spawn_new_box(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::AZURE.into()),
+        mass,
+        size,
+        size,
+        size,
+        Transform::from_xyz(1.0, -2.0, 0.0),
+        Vector::ZERO,
+        Vector::ZERO,
+    );
+    commands.entity(bob).insert(
+        RigidBody::new_box(mass, size, size, size, Vector::ZERO, Vector::ZERO)
+            .with_drag(Drag::new_box(1.05, size, size, size)),
+    );
+    DistanceConstraintBundle::spawn_new(
+        &mut commands,
+        &mut meshes,
+        materials.add(Color::AZURE.into()),
+        pivot,
+        bob,
+        Vector::ZERO,
+        Vector::ZERO,
+        2.0,
+        2.0,
+    );
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            color: Color::WHITE,
+            illuminance: 10_000.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // camera
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 0.0, 15.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        PanOrbitCamera::default(),
+    ));
+}
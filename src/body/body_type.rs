@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::math::{Real, Vector};
+
+use super::PhysicsTransform;
+
+/// How a body moves.
+///
+/// A [`super::Body`] without this component is dynamic if it has a [`super::RigidBody`] and
+/// static otherwise.
+#[derive(Component, Clone, Copy, PartialEq, Debug, Default)]
+pub enum BodyType {
+    /// Moved by forces, springs and constraints. Requires a [`super::RigidBody`].
+    #[default]
+    Dynamic,
+    /// Never moves, e.g. an anchor.
+    Static,
+    /// Moves with a prescribed velocity and is not affected by forces, springs and constraints.
+    /// Connected bodies feel its real velocity, e.g. a driven pendulum pivot.
+    ///
+    /// Velocities are given in **World** coordinates, the body rotates around its origin.
+    Kinematic {
+        velocity: Vector,
+        angular_velocity: Vector,
+    },
+}
+
+impl BodyType {
+    /// Returns a kinematic body type that moves a body from `from` to `to` during `dt`.
+    /// Set it before every step to make a body follow a path.
+    pub fn kinematic_towards(from: &PhysicsTransform, to: &PhysicsTransform, dt: Real) -> Self {
+        let mut rotation = to.rotation * from.rotation.inverse();
+        // Take the shortest way
+        if rotation.w < 0.0 {
+            rotation = -rotation;
+        }
+        Self::Kinematic {
+            velocity: (to.translation - from.translation) / dt,
+            angular_velocity: rotation.to_scaled_axis() / dt,
+        }
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        *self == Self::Dynamic
+    }
+}
@@ -1,114 +1,330 @@
 use bevy::prelude::*;
 
-use crate::energy::Energy;
+use crate::{
+    energy::Energy,
+    math::{to_f32, Real, Vector},
+};
 
-use super::{rigid_body::RigidBody, Body};
+use super::{
+    compound::CompoundBody,
+    mass_properties::MassProperties,
+    rigid_body::RigidBody,
+    shape::{Cone, Ellipsoid},
+    Body, BodyType, PhysicsTransform,
+};
 
 #[derive(Bundle, Default)]
 pub struct RigidBodyBundle {
     pbr_bundle: PbrBundle,
     body: Body,
+    body_type: BodyType,
+    physics_transform: PhysicsTransform,
     rigid_body: RigidBody,
     energy: Energy,
 }
 
+// Shape helpers take the dimensions of a shape along with the parameters of `spawn`
+#[allow(clippy::too_many_arguments)]
 impl RigidBodyBundle {
-    /// Creates a new rigid body box.
-    fn new_box(
-        pbr_bundle: PbrBundle,
-        mass: f32,
-        x_length: f32,
-        y_length: f32,
-        z_length: f32,
-        pulse: Vec3,
-        angular_momentum: Vec3,
-    ) -> Self {
+    /// Creates a new bundle of the `rigid_body` rendered with the `pbr_bundle`.
+    fn new(pbr_bundle: PbrBundle, rigid_body: RigidBody) -> Self {
         Self {
+            physics_transform: PhysicsTransform::from(pbr_bundle.transform),
             pbr_bundle,
             body: Body,
-            rigid_body: RigidBody::new_box(
-                mass,
-                x_length,
-                y_length,
-                z_length,
-                pulse,
-                angular_momentum,
-            ),
+            body_type: BodyType::Dynamic,
+            rigid_body,
             energy: Energy::default(),
         }
     }
 
-    fn new_sphere(
-        pbr_bundle: PbrBundle,
-        mass: f32,
-        r: f32,
-        pulse: Vec3,
-        angular_momentum: Vec3,
-    ) -> Self {
-        Self {
-            pbr_bundle,
-            body: Body,
-            rigid_body: RigidBody::new_sphere(mass, r, pulse, angular_momentum),
-            energy: Energy::default(),
-        }
-    }
-
-    pub fn spawn_new_box(
+    /// Spawns the `rigid_body` rendered with the `mesh`, e.g. a body with damping or drag.
+    /// The `spawn_new_*` helpers spawn bodies of shapes with matching meshes.
+    pub fn spawn(
         commands: &mut Commands,
         meshes: &mut ResMut<Assets<Mesh>>,
         material: Handle<StandardMaterial>,
-        mass: f32,
-        x_length: f32,
-        y_length: f32,
-        z_length: f32,
+        mesh: Mesh,
+        rigid_body: RigidBody,
         transform: Transform,
-        pulse: Vec3,
-        angular_momentum: Vec3,
     ) -> Entity {
         commands
-            .spawn(Self::new_box(
+            .spawn(Self::new(
                 PbrBundle {
-                    mesh: meshes.add(Mesh::from(shape::Box::new(x_length, y_length, z_length))),
+                    mesh: meshes.add(mesh),
                     material,
                     transform,
                     ..default()
                 },
-                mass,
-                x_length,
-                y_length,
-                z_length,
-                pulse,
-                angular_momentum,
+                rigid_body,
             ))
             .id()
     }
 
+    pub fn spawn_new_box(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mass: Real,
+        x_length: Real,
+        y_length: Real,
+        z_length: Real,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Entity {
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(shape::Box::new(
+                to_f32(x_length),
+                to_f32(y_length),
+                to_f32(z_length),
+            )),
+            RigidBody::new_box(mass, x_length, y_length, z_length, pulse, angular_momentum),
+            transform,
+        )
+    }
+
     pub fn spawn_new_sphere(
         commands: &mut Commands,
         meshes: &mut ResMut<Assets<Mesh>>,
         material: Handle<StandardMaterial>,
-        mass: f32,
-        r: f32,
+        mass: Real,
+        r: Real,
         transform: Transform,
-        pulse: Vec3,
-        angular_momentum: Vec3,
+        pulse: Vector,
+        angular_momentum: Vector,
     ) -> Entity {
-        commands
-            .spawn(Self::new_sphere(
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(shape::UVSphere {
+                radius: to_f32(r),
+                ..default()
+            }),
+            RigidBody::new_sphere(mass, r, pulse, angular_momentum),
+            transform,
+        )
+    }
+
+    pub fn spawn_new_hollow_sphere(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mass: Real,
+        r: Real,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Entity {
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(shape::UVSphere {
+                radius: to_f32(r),
+                ..default()
+            }),
+            RigidBody::new_hollow_sphere(mass, r, pulse, angular_momentum),
+            transform,
+        )
+    }
+
+    pub fn spawn_new_ellipsoid(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mass: Real,
+        a: Real,
+        b: Real,
+        c: Real,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Entity {
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(Ellipsoid {
+                a: to_f32(a),
+                b: to_f32(b),
+                c: to_f32(c),
+                ..default()
+            }),
+            RigidBody::new_ellipsoid(mass, a, b, c, pulse, angular_momentum),
+            transform,
+        )
+    }
+
+    pub fn spawn_new_cylinder(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mass: Real,
+        r: Real,
+        height: Real,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Entity {
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(shape::Cylinder {
+                radius: to_f32(r),
+                height: to_f32(height),
+                resolution: 32,
+                ..default()
+            }),
+            RigidBody::new_cylinder(mass, r, height, pulse, angular_momentum),
+            transform,
+        )
+    }
+
+    pub fn spawn_new_capsule(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mass: Real,
+        r: Real,
+        depth: Real,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Entity {
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(shape::Capsule {
+                radius: to_f32(r),
+                depth: to_f32(depth),
+                ..default()
+            }),
+            RigidBody::new_capsule(mass, r, depth, pulse, angular_momentum),
+            transform,
+        )
+    }
+
+    pub fn spawn_new_cone(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mass: Real,
+        r: Real,
+        height: Real,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Entity {
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(Cone {
+                radius: to_f32(r),
+                height: to_f32(height),
+                ..default()
+            }),
+            RigidBody::new_cone(mass, r, height, pulse, angular_momentum),
+            transform,
+        )
+    }
+
+    /// Spawns a thin rod. The rod is rendered as a cylinder of `thickness`, which does not affect
+    /// its inertia.
+    pub fn spawn_new_rod(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mass: Real,
+        length: Real,
+        thickness: f32,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Entity {
+        Self::spawn(
+            commands,
+            meshes,
+            material,
+            Mesh::from(shape::Cylinder {
+                radius: thickness / 2.0,
+                height: to_f32(length),
+                ..default()
+            }),
+            RigidBody::new_rod(mass, length, pulse, angular_momentum),
+            transform,
+        )
+    }
+
+    /// Spawns a solid body of uniform `density` bounded by the `mesh`, see
+    /// [`MassProperties::from_mesh`]. `transform` places the origin of the mesh, the center of
+    /// mass of the body is offset from it. Returns `None` if mass properties of the `mesh` can not
+    /// be computed.
+    pub fn spawn_new_mesh(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        mesh: Mesh,
+        density: Real,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Option<Entity> {
+        let mass_properties = MassProperties::from_mesh(&mesh, density)?;
+        Some(Self::spawn(
+            commands,
+            meshes,
+            material,
+            mesh,
+            mass_properties.rigid_body(pulse, angular_momentum),
+            transform,
+        ))
+    }
+
+    /// Spawns a compound body made of `parts`, see [`CompoundBody`]. Every part has a mesh, mass
+    /// properties and a transform relative to the origin of the compound, and is rendered by a
+    /// child entity. `transform` places the origin of the compound. Returns `None` if there are
+    /// no `parts`.
+    pub fn spawn_new_compound(
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        material: Handle<StandardMaterial>,
+        parts: Vec<(Mesh, MassProperties, Transform)>,
+        transform: Transform,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Option<Entity> {
+        let compound = parts.iter().fold(
+            CompoundBody::new(),
+            |compound, (_, part, part_transform)| compound.with_part(*part, *part_transform),
+        );
+        let rigid_body = compound.rigid_body(pulse, angular_momentum)?;
+        let entity = commands
+            .spawn(Self::new(
                 PbrBundle {
-                    mesh: meshes.add(Mesh::from(shape::UVSphere {
-                        radius: r,
-                        ..default()
-                    })),
-                    material,
                     transform,
                     ..default()
                 },
-                mass,
-                r,
-                pulse,
-                angular_momentum,
+                rigid_body,
             ))
-            .id()
+            .with_children(|parent| {
+                for (mesh, _, part_transform) in parts {
+                    parent.spawn(PbrBundle {
+                        mesh: meshes.add(mesh),
+                        material: material.clone(),
+                        transform: part_transform,
+                        ..default()
+                    });
+                }
+            })
+            .id();
+        Some(entity)
     }
 }
@@ -0,0 +1,55 @@
+use crate::math::Vector;
+
+use super::{mass_properties::MassProperties, PhysicsTransform, RigidBody};
+
+/// A body made of several rigidly connected parts.
+///
+/// Every part has mass properties (e.g. of a [`RigidBody`] created with one of the shape
+/// constructors) and a transform relative to the origin of the compound. The compound body keeps
+/// its origin, and its center of mass is offset from it, see [`CompoundBody::center_of_mass`].
+#[derive(Clone, Default, Debug)]
+pub struct CompoundBody {
+    parts: Vec<(MassProperties, PhysicsTransform)>,
+}
+
+impl CompoundBody {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `part` placed at `transform` relative to the origin of the compound.
+    pub fn with_part(
+        mut self,
+        part: impl Into<MassProperties>,
+        transform: impl Into<PhysicsTransform>,
+    ) -> Self {
+        self.parts.push((part.into(), transform.into()));
+        self
+    }
+
+    pub fn parts(&self) -> &[(MassProperties, PhysicsTransform)] {
+        &self.parts
+    }
+
+    /// Combined mass properties of all parts. The center of mass is given relative to the origin
+    /// of the compound. Returns `None` if the compound has no parts, see
+    /// [`MassProperties::combine`].
+    pub fn mass_properties(&self) -> Option<MassProperties> {
+        MassProperties::combine(
+            self.parts
+                .iter()
+                .map(|(part, transform)| part.transformed(transform)),
+        )
+    }
+
+    /// Center of mass of all parts relative to the origin of the compound.
+    pub fn center_of_mass(&self) -> Option<Vector> {
+        Some(self.mass_properties()?.center_of_mass)
+    }
+
+    /// Creates a new body with combined mass properties of all parts, a `pulse` and an
+    /// `angular_momentum`. The origin of the body is the origin of the compound.
+    pub fn rigid_body(&self, pulse: Vector, angular_momentum: Vector) -> Option<RigidBody> {
+        Some(self.mass_properties()?.rigid_body(pulse, angular_momentum))
+    }
+}
@@ -0,0 +1,83 @@
+use crate::math::{consts::PI, Quaternion, Real, Vector};
+
+/// Shape of a body as seen by the air, used to find its cross-section.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DragShape {
+    Sphere {
+        radius: Real,
+    },
+    /// A box with given lengths along the X, Y and Z axes of the body
+    Box {
+        x_length: Real,
+        y_length: Real,
+        z_length: Real,
+    },
+}
+
+/// Quadratic aerodynamic drag.
+///
+/// A body moving through the air with a relative velocity `v` feels a force
+/// `-air_density * coefficient * area * |v| * v / 2` acting at its center of mass, where `area`
+/// is its cross-section orthogonal to `v`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Drag {
+    /// Drag coefficient, e.g. about 0.47 for a sphere and 1.05 for a cube
+    pub coefficient: Real,
+    pub shape: DragShape,
+}
+
+impl Drag {
+    /// Creates a drag of a sphere of radius `r`.
+    pub fn new_sphere(coefficient: Real, r: Real) -> Self {
+        Self {
+            coefficient,
+            shape: DragShape::Sphere { radius: r },
+        }
+    }
+
+    /// Creates a drag of a box with given `length`s.
+    pub fn new_box(coefficient: Real, x_length: Real, y_length: Real, z_length: Real) -> Self {
+        Self {
+            coefficient,
+            shape: DragShape::Box {
+                x_length,
+                y_length,
+                z_length,
+            },
+        }
+    }
+
+    /// Returns the area of the projection of the shape onto a plane orthogonal to `direction`.
+    /// `direction` is a unit vector in **Body** coordinates.
+    pub fn cross_section(&self, direction: Vector) -> Real {
+        match self.shape {
+            DragShape::Sphere { radius } => PI * radius.powi(2),
+            // Every pair of opposite faces is seen at the cosine of the angle to its normal
+            DragShape::Box {
+                x_length,
+                y_length,
+                z_length,
+            } => {
+                direction.x.abs() * y_length * z_length
+                    + direction.y.abs() * x_length * z_length
+                    + direction.z.abs() * x_length * y_length
+            }
+        }
+    }
+
+    /// Computes the drag force in **World** coordinates for a body with given `rotation` moving
+    /// with `relative_velocity` to the air of `air_density`.
+    pub fn compute_force(
+        &self,
+        relative_velocity: Vector,
+        rotation: Quaternion,
+        air_density: Real,
+    ) -> Vector {
+        let speed = relative_velocity.length();
+        if speed == 0.0 {
+            return Vector::ZERO;
+        }
+        let area = self.cross_section(rotation.inverse() * (relative_velocity / speed));
+        -0.5 * air_density * self.coefficient * area * speed * relative_velocity
+    }
+}
@@ -0,0 +1,148 @@
+use bevy::{
+    math::Vec3,
+    render::{
+        mesh::{Mesh, VertexAttributeValues},
+        render_resource::PrimitiveTopology,
+    },
+};
+
+use crate::math::{from_vec3, Matrix, Real, Vector};
+
+use super::{PhysicsTransform, RigidBody};
+
+/// Mass, center of mass and inertia tensor of a solid body.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MassProperties {
+    pub mass: Real,
+    /// Center of mass relative to the origin of the mesh, in mesh coordinates
+    pub center_of_mass: Vector,
+    /// Inertia tensor around the center of mass, with axes of the mesh
+    pub inertia_tensor: Matrix,
+}
+
+impl MassProperties {
+    /// Computes mass properties of a solid of uniform `density` bounded by a closed triangle
+    /// `mesh`.
+    ///
+    /// The solid is split into tetrahedra formed by the origin and every triangle, and their signed
+    /// volumes and covariance matrices are summed up (see Jonathan Blow, Atman J Binstock,
+    /// "How to find the inertia tensor (or other mass properties) of a 3D solid body represented
+    /// by a triangle mesh"). Both outward and inward facing triangles are accepted as long as all
+    /// of them face the same way.
+    ///
+    /// Returns `None` if the mesh is not a triangle list, has no positions or encloses no volume.
+    pub fn from_mesh(mesh: &Mesh, density: Real) -> Option<Self> {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            return None;
+        };
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(indices) => indices.iter().collect(),
+            None => (0..positions.len()).collect(),
+        };
+
+        // Covariance of the canonical tetrahedron (0, X, Y, Z)
+        let canonical_covariance = Matrix::from_cols(
+            Vector::new(2.0, 1.0, 1.0),
+            Vector::new(1.0, 2.0, 1.0),
+            Vector::new(1.0, 1.0, 2.0),
+        ) * (1.0 / 120.0);
+
+        let mut volume = 0.0;
+        let mut first_moment = Vector::ZERO;
+        let mut covariance = Matrix::ZERO;
+        for triangle in indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| from_vec3(Vec3::from(positions[triangle[i]])));
+            // Columns are the vertices, so the determinant is six times the signed volume
+            let vertices = Matrix::from_cols(a, b, c);
+            let determinant = vertices.determinant();
+            volume += determinant / 6.0;
+            first_moment += determinant / 6.0 * (a + b + c) / 4.0;
+            covariance += determinant * vertices * canonical_covariance * vertices.transpose();
+        }
+        if volume == 0.0 {
+            return None;
+        }
+        // Inward facing triangles give negative volumes, all the sums flip the sign together
+        let sign = volume.signum();
+        let volume = volume * sign;
+        let mass = density * volume;
+        let center_of_mass = first_moment * sign / volume;
+
+        // Covariance around the center of mass (parallel axis theorem)
+        let covariance =
+            covariance * (density * sign) - mass * outer_product(center_of_mass, center_of_mass);
+        let trace = covariance.x_axis.x + covariance.y_axis.y + covariance.z_axis.z;
+        let inertia_tensor = Matrix::IDENTITY * trace - covariance;
+
+        Some(Self {
+            mass,
+            center_of_mass,
+            inertia_tensor,
+        })
+    }
+
+    /// Returns mass properties of the same solid moved by the `transform`.
+    pub fn transformed(&self, transform: &PhysicsTransform) -> Self {
+        let rotation = Matrix::from_quat(transform.rotation);
+        Self {
+            mass: self.mass,
+            center_of_mass: transform.translation + transform.rotation * self.center_of_mass,
+            inertia_tensor: rotation * self.inertia_tensor * rotation.transpose(),
+        }
+    }
+
+    /// Combines mass properties of several `parts` into mass properties of one solid.
+    ///
+    /// Inertia tensors of the parts are moved to the common center of mass with the parallel
+    /// axis theorem: `I = I_part + m (|d|^2 E - d d^T)`, where `d` is the offset of the part.
+    ///
+    /// Returns `None` if there are no parts or their total mass is not positive.
+    pub fn combine(parts: impl IntoIterator<Item = MassProperties>) -> Option<Self> {
+        let parts: Vec<_> = parts.into_iter().collect();
+        let mass: Real = parts.iter().map(|p| p.mass).sum();
+        if mass <= 0.0 {
+            return None;
+        }
+        let center_of_mass = parts
+            .iter()
+            .fold(Vector::ZERO, |acc, p| acc + p.mass * p.center_of_mass)
+            / mass;
+        let inertia_tensor = parts.iter().fold(Matrix::ZERO, |acc, p| {
+            let d = p.center_of_mass - center_of_mass;
+            acc + p.inertia_tensor
+                + p.mass * (Matrix::IDENTITY * d.length_squared() - outer_product(d, d))
+        });
+        Some(Self {
+            mass,
+            center_of_mass,
+            inertia_tensor,
+        })
+    }
+
+    /// Creates a new body with these mass properties, a `pulse` and an `angular_momentum`.
+    /// The origin of the body is the origin of the mesh.
+    pub fn rigid_body(&self, pulse: Vector, angular_momentum: Vector) -> RigidBody {
+        RigidBody::new(self.mass, self.inertia_tensor, pulse, angular_momentum)
+            .with_center_of_mass(self.center_of_mass)
+    }
+}
+
+/// Returns `a * b^T`.
+fn outer_product(a: Vector, b: Vector) -> Matrix {
+    Matrix::from_cols(a * b.x, a * b.y, a * b.z)
+}
+
+impl From<RigidBody> for MassProperties {
+    fn from(body: RigidBody) -> Self {
+        Self {
+            mass: body.mass,
+            center_of_mass: body.center_of_mass,
+            inertia_tensor: body.intertia_tensor_body,
+        }
+    }
+}
@@ -1,9 +1,19 @@
 use bevy::prelude::*;
 
+pub use body_type::BodyType;
+pub use physics_transform::PhysicsTransform;
 pub use rigid_body::RigidBody;
 
+use crate::math::Vector;
+
+pub mod body_type;
 pub mod bundle;
+pub mod compound;
+pub mod drag;
+pub mod mass_properties;
+pub mod physics_transform;
 pub mod rigid_body;
+pub mod shape;
 
 /// A marker to show that entity is actually a body
 #[derive(Component, Default)]
@@ -11,15 +21,19 @@ pub struct Body;
 
 impl Body {
     /// Converts a point in **Body** coordinates to **World** coordinates.
-    pub fn body_to_world_coordinates(&self, body_coordinates: Vec3, transform: &Transform) -> Vec3 {
+    pub fn body_to_world_coordinates(
+        &self,
+        body_coordinates: Vector,
+        transform: &PhysicsTransform,
+    ) -> Vector {
         return transform.translation + transform.rotation.mul_vec3(body_coordinates);
     }
 
     pub fn world_to_body_coordinates(
         &self,
-        world_coordinates: Vec3,
-        transform: &Transform,
-    ) -> Vec3 {
+        world_coordinates: Vector,
+        transform: &PhysicsTransform,
+    ) -> Vector {
         return transform
             .rotation
             .inverse()
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::math::{from_quat, from_vec3, to_quat, to_vec3, Quaternion, Real, Vector};
+
+use super::Body;
+
+/// Position and orientation of a body used by the simulation.
+///
+/// It has the precision of the simulation core (see [`crate::math`]), while `Transform` is only
+/// a single precision copy for rendering. The simulation writes both after every step.
+/// If `Transform` of a body is changed by another system (e.g. to teleport the body), the
+/// simulation picks the change up, see [`PhysicsTransform::current`].
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub struct PhysicsTransform {
+    pub translation: Vector,
+    pub rotation: Quaternion,
+}
+
+impl Default for PhysicsTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Transform> for PhysicsTransform {
+    fn from(transform: Transform) -> Self {
+        Self {
+            translation: from_vec3(transform.translation),
+            rotation: from_quat(transform.rotation),
+        }
+    }
+}
+
+impl PhysicsTransform {
+    pub const IDENTITY: Self = Self {
+        translation: Vector::ZERO,
+        rotation: Quaternion::IDENTITY,
+    };
+
+    pub fn from_translation(translation: Vector) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn from_xyz(x: Real, y: Real, z: Real) -> Self {
+        Self::from_translation(Vector::new(x, y, z))
+    }
+
+    pub fn from_rotation(rotation: Quaternion) -> Self {
+        Self {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Copies translation and rotation to a render `transform`, keeping its scale.
+    pub fn write_to(&self, transform: &mut Transform) {
+        transform.translation = to_vec3(self.translation);
+        transform.rotation = to_quat(self.rotation);
+    }
+
+    /// Whether a render `transform` is what [`PhysicsTransform::write_to`] would write.
+    pub fn matches(&self, transform: &Transform) -> bool {
+        transform.translation == to_vec3(self.translation)
+            && transform.rotation == to_quat(self.rotation)
+    }
+
+    /// Returns the physics transform of a body with a render `transform`.
+    ///
+    /// It is `physics_transform` if it is in sync with `transform`, or is made from `transform`
+    /// if the body has no [`PhysicsTransform`] yet or was moved by changing its `Transform`.
+    pub fn current(physics_transform: Option<&PhysicsTransform>, transform: &Transform) -> Self {
+        match physics_transform {
+            Some(physics_transform) if physics_transform.matches(transform) => *physics_transform,
+            _ => Self::from(*transform),
+        }
+    }
+}
+
+/// Adds [`PhysicsTransform`] made from `Transform` to bodies that do not have it.
+#[allow(clippy::type_complexity)]
+pub fn insert_physics_transforms(
+    mut commands: Commands,
+    bodies: Query<(Entity, &Transform), (With<Body>, Without<PhysicsTransform>)>,
+) {
+    for (entity, transform) in bodies.iter() {
+        commands
+            .entity(entity)
+            .insert(PhysicsTransform::from(*transform));
+    }
+}
@@ -1,50 +1,83 @@
 use bevy::prelude::*;
 
-use super::Body;
+use crate::{
+    math::{Matrix, Real, Vector},
+    wind::Wind,
+};
+
+use super::{drag::Drag, Body, PhysicsTransform};
 
 #[derive(Component, Clone, Copy)]
 pub struct RigidBody {
     /* Constant values */
     /// Mass of a body
-    pub mass: f32,
-    /// Intertia tensor of a body in **Body** coordinates
-    pub intertia_tensor_body: Mat3,
+    pub mass: Real,
+    /// Intertia tensor of a body around its center of mass in **Body** coordinates
+    pub intertia_tensor_body: Matrix,
+    /// Center of mass of a body in **Body** coordinates. The origin of a body (e.g. of its mesh)
+    /// may differ from its center of mass
+    pub center_of_mass: Vector,
+    /// Linear damping coefficient. A body is slowed down by a force
+    /// `-linear_damping * velocity` acting at its center of mass, e.g. to model air resistance
+    pub linear_damping: Real,
+    /// Angular damping coefficient. A body is slowed down by a torque
+    /// `-angular_damping * angular_velocity`
+    pub angular_damping: Real,
+    /// Quadratic aerodynamic drag of a body, see [`crate::wind::Wind`]
+    pub drag: Option<Drag>,
 
     /* State variables */
     /// Pulse of a body in **World** coordinates
-    pub pulse: Vec3,
+    pub pulse: Vector,
     /// Angular momentum of a body in **World** coordinates
-    pub angular_momentum: Vec3,
+    pub angular_momentum: Vector,
     /// Total external force acting on a body in **World** coordinates
-    pub force: Vec3,
+    pub force: Vector,
     /// Total external torque action on a body in **World** coordinates
-    pub torque: Vec3,
+    pub torque: Vector,
+    /// Whether a body is sleeping. Sleeping bodies are not integrated until they are disturbed,
+    /// see [`crate::settings::SettingsResource::allow_sleeping`]
+    pub sleeping: bool,
+    /// For how long velocities of a body have been below sleep thresholds
+    pub resting_time: Real,
+    /// Energy dissipated by damping and drag of a body so far, including kinetic energy it had
+    /// when it fell asleep. Kinetic and potential energies of a body plus this energy stay
+    /// constant. It decreases when wind does work on a body
+    pub dissipated_energy: Real,
 
     /* Derived variables */
     /// Inversed inertia tensor of a body in **Body coordinates**
     /// Inertia_tensor_body_inv = Intertia_tensor_body^{-1}
-    pub intertia_tensor_body_inv: Mat3,
+    pub intertia_tensor_body_inv: Matrix,
 }
 
 impl Default for RigidBody {
     fn default() -> Self {
         let mass = 1.0;
         let size = 2.0;
-        let inertia_tensor = Mat3::IDENTITY * (mass * size * size / 6.0);
+        let inertia_tensor = Matrix::IDENTITY * (mass * size * size / 6.0);
         return Self {
             mass,
             intertia_tensor_body: inertia_tensor,
-            pulse: Vec3::ZERO,
-            angular_momentum: Vec3::ZERO,
-            force: Vec3::ZERO,
-            torque: Vec3::ZERO,
+            center_of_mass: Vector::ZERO,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            drag: None,
+            pulse: Vector::ZERO,
+            angular_momentum: Vector::ZERO,
+            force: Vector::ZERO,
+            torque: Vector::ZERO,
+            sleeping: false,
+            resting_time: 0.0,
+            dissipated_energy: 0.0,
             intertia_tensor_body_inv: inertia_tensor.inverse(),
         };
     }
 }
 
 impl RigidBody {
-    pub fn get_velocity(&self) -> Vec3 {
+    /// Returns velocity of the center of mass of the body.
+    pub fn get_velocity(&self) -> Vector {
         return self.pulse / self.mass;
     }
 
@@ -52,16 +85,82 @@ impl RigidBody {
     /// Both `application_point` and `force` are given in **World** coordinates.
     pub fn apply_force(
         &mut self,
-        transform: &Transform,
-        application_point_world: Vec3,
-        force: Vec3,
+        transform: &PhysicsTransform,
+        application_point_world: Vector,
+        force: Vector,
     ) {
         self.force += force;
-        self.torque += (application_point_world - transform.translation).cross(force);
+        self.torque += (application_point_world - self.get_center_of_mass(transform)).cross(force);
+    }
+
+    /// Returns the center of mass of the body in **World** coordinates.
+    pub fn get_center_of_mass(&self, transform: &PhysicsTransform) -> Vector {
+        Body.body_to_world_coordinates(self.center_of_mass, transform)
+    }
+
+    /// Returns the body with its center of mass at `center_of_mass` in **Body** coordinates.
+    /// The inertia tensor is still taken around the center of mass.
+    pub fn with_center_of_mass(self, center_of_mass: Vector) -> Self {
+        Self {
+            center_of_mass,
+            ..self
+        }
+    }
+
+    /// Returns the body with given `linear_damping` and `angular_damping` coefficients.
+    pub fn with_damping(self, linear_damping: Real, angular_damping: Real) -> Self {
+        Self {
+            linear_damping,
+            angular_damping,
+            ..self
+        }
+    }
+
+    /// Returns the body with given aerodynamic `drag`.
+    pub fn with_drag(self, drag: Drag) -> Self {
+        Self {
+            drag: Some(drag),
+            ..self
+        }
+    }
+
+    /// Whether the body loses energy to damping or drag.
+    pub fn is_damped(&self) -> bool {
+        self.linear_damping != 0.0 || self.angular_damping != 0.0 || self.drag.is_some()
+    }
+
+    /// Applies damping force and torque to the body.
+    pub fn apply_damping(&mut self, transform: &PhysicsTransform) {
+        self.force -= self.linear_damping * self.get_velocity();
+        self.torque -= self.angular_damping * self.get_angular_velocity(transform);
+    }
+
+    /// Computes the drag force acting at the center of mass of the body in the `wind`.
+    pub fn compute_drag_force(&self, transform: &PhysicsTransform, wind: &Wind) -> Vector {
+        let Some(drag) = self.drag else {
+            return Vector::ZERO;
+        };
+        let wind_velocity = wind.velocity_at(self.get_center_of_mass(transform));
+        drag.compute_force(
+            self.get_velocity() - wind_velocity,
+            transform.rotation,
+            wind.air_density,
+        )
+    }
+
+    /// Whether the `wind` blows at the body with drag, so that drag pushes it even at rest.
+    pub fn is_in_wind(&self, transform: &PhysicsTransform, wind: &Wind) -> bool {
+        self.drag.is_some() && wind.velocity_at(self.get_center_of_mass(transform)) != Vector::ZERO
+    }
+
+    /// Computes power dissipated by damping of the body, i.e. energy lost per second.
+    pub fn compute_damping_power(&self, transform: &PhysicsTransform) -> Real {
+        self.linear_damping * self.get_velocity().length_squared()
+            + self.angular_damping * self.get_angular_velocity(transform).length_squared()
     }
 
     /// Computes keenetic energy of the body
-    pub fn compute_keenetic_energy(&self, transform: &Transform) -> f32 {
+    pub fn compute_keenetic_energy(&self, transform: &PhysicsTransform) -> Real {
         let linear_component = 0.5 * self.pulse.length_squared() / self.mass;
         let omega = self.get_angular_velocity(transform);
         let angular_component = 0.5 * self.angular_momentum.dot(omega);
@@ -69,12 +168,12 @@ impl RigidBody {
     }
 
     /// Computes potential energy of the body in the field of force of `gravity`.
-    pub fn compute_potential_energy(&self, transform: &Transform, gravity: Vec3) -> f32 {
-        (transform.translation.y) * self.mass * gravity.length()
+    pub fn compute_potential_energy(&self, transform: &PhysicsTransform, gravity: Vector) -> Real {
+        self.get_center_of_mass(transform).y * self.mass * gravity.length()
     }
 
     /// Computes total energy of the body
-    pub fn compute_energy(&self, transform: &Transform, gravity: Vec3) -> f32 {
+    pub fn compute_energy(&self, transform: &PhysicsTransform, gravity: Vector) -> Real {
         self.compute_potential_energy(transform, gravity) + self.compute_keenetic_energy(transform)
     }
 
@@ -83,23 +182,23 @@ impl RigidBody {
     ///
     /// Total velocity of a particle is a sum of its linear velocity (which equals to body velocity)
     /// and angular velocity.
-    pub fn get_particle_velocity(&self, particle: Vec3, transform: &Transform) -> Vec3 {
+    pub fn get_particle_velocity(&self, particle: Vector, transform: &PhysicsTransform) -> Vector {
         let angular_velocity = self.get_angular_velocity(transform);
-        self.get_velocity() + angular_velocity.cross(particle - transform.translation)
+        self.get_velocity() + angular_velocity.cross(particle - self.get_center_of_mass(transform))
     }
 
-    pub fn get_angular_velocity(&self, transform: &Transform) -> Vec3 {
+    pub fn get_angular_velocity(&self, transform: &PhysicsTransform) -> Vector {
         self.get_inertia_tensor_inv(transform) * self.angular_momentum
     }
 
-    pub fn get_inertia_tensor(&self, transform: &Transform) -> Mat3 {
-        let r = Mat3::from_quat(transform.rotation);
+    pub fn get_inertia_tensor(&self, transform: &PhysicsTransform) -> Matrix {
+        let r = Matrix::from_quat(transform.rotation);
         let i = r * self.intertia_tensor_body * r.transpose();
         i
     }
 
-    pub fn get_inertia_tensor_inv(&self, transform: &Transform) -> Mat3 {
-        let r = Mat3::from_quat(transform.rotation);
+    pub fn get_inertia_tensor_inv(&self, transform: &PhysicsTransform) -> Matrix {
+        let r = Matrix::from_quat(transform.rotation);
         let iinv = r * self.intertia_tensor_body_inv * r.transpose();
         iinv
     }
@@ -109,7 +208,11 @@ impl RigidBody {
     ///
     /// Total velocity of a particle is a sum of its linear velocity (which equals to body velocity)
     /// and angular velocity.
-    pub fn get_particle_body_velocity(&self, particle_body: Vec3, transform: &Transform) -> Vec3 {
+    pub fn get_particle_body_velocity(
+        &self,
+        particle_body: Vector,
+        transform: &PhysicsTransform,
+    ) -> Vector {
         let particle = Body.body_to_world_coordinates(particle_body, transform);
         self.get_particle_velocity(particle, transform)
     }
@@ -119,9 +222,9 @@ impl RigidBody {
     /// coordinates.
     pub fn apply_force_body_coords(
         &mut self,
-        application_point_body: Vec3,
-        force: Vec3,
-        transform: &Transform,
+        application_point_body: Vector,
+        force: Vector,
+        transform: &PhysicsTransform,
     ) {
         let application_point_world =
             Body.body_to_world_coordinates(application_point_body, transform);
@@ -129,14 +232,26 @@ impl RigidBody {
     }
 
     /// Creates a new body with given `inertia_tensor_body`, `mass`, `pulse` and `angular momentum`.
-    pub fn new(mass: f32, intertia_tensor_body: Mat3, pulse: Vec3, angular_momentum: Vec3) -> Self {
+    pub fn new(
+        mass: Real,
+        intertia_tensor_body: Matrix,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Self {
         Self {
             mass,
             intertia_tensor_body,
+            center_of_mass: Vector::ZERO,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            drag: None,
             pulse,
             angular_momentum,
-            force: Vec3::ZERO,
-            torque: Vec3::ZERO,
+            force: Vector::ZERO,
+            torque: Vector::ZERO,
+            sleeping: false,
+            resting_time: 0.0,
+            dissipated_energy: 0.0,
             intertia_tensor_body_inv: intertia_tensor_body.inverse(),
         }
     }
@@ -144,23 +259,123 @@ impl RigidBody {
     /// Creates a new body wih an inertia tensor of a box with given `mass`, `length`s, `pulse` and
     /// `angular_momentum`.
     pub fn new_box(
-        mass: f32,
-        x_length: f32,
-        y_length: f32,
-        z_length: f32,
-        pulse: Vec3,
-        angular_momentum: Vec3,
+        mass: Real,
+        x_length: Real,
+        y_length: Real,
+        z_length: Real,
+        pulse: Vector,
+        angular_momentum: Vector,
     ) -> Self {
-        let inertia_tensor = Mat3::from_cols(
-            Vec3::from_array([y_length.powi(2) + z_length.powi(2), 0.0, 0.0]),
-            Vec3::from_array([0.0, x_length.powi(2) + z_length.powi(2), 0.0]),
-            Vec3::from_array([0.0, 0.0, x_length.powi(2) + y_length.powi(2)]),
+        let inertia_tensor = Matrix::from_diagonal(
+            mass / 12.0
+                * Vector::new(
+                    y_length.powi(2) + z_length.powi(2),
+                    x_length.powi(2) + z_length.powi(2),
+                    x_length.powi(2) + y_length.powi(2),
+                ),
         );
         RigidBody::new(mass, inertia_tensor, pulse, angular_momentum)
     }
 
-    pub fn new_sphere(mass: f32, r: f32, pulse: Vec3, angular_momentum: Vec3) -> Self {
-        let inertia_tensor = 2.0 / 5.0 * mass * r.powi(2) * Mat3::IDENTITY;
+    /// Creates a new body with an inertia tensor of a solid sphere of radius `r`.
+    pub fn new_sphere(mass: Real, r: Real, pulse: Vector, angular_momentum: Vector) -> Self {
+        let inertia_tensor = 2.0 / 5.0 * mass * r.powi(2) * Matrix::IDENTITY;
+        RigidBody::new(mass, inertia_tensor, pulse, angular_momentum)
+    }
+
+    /// Creates a new body with an inertia tensor of a thin spherical shell of radius `r`.
+    pub fn new_hollow_sphere(mass: Real, r: Real, pulse: Vector, angular_momentum: Vector) -> Self {
+        let inertia_tensor = 2.0 / 3.0 * mass * r.powi(2) * Matrix::IDENTITY;
+        RigidBody::new(mass, inertia_tensor, pulse, angular_momentum)
+    }
+
+    /// Creates a new body with an inertia tensor of a solid ellipsoid with semi-axes `a`, `b` and
+    /// `c` along the X, Y and Z axes.
+    pub fn new_ellipsoid(
+        mass: Real,
+        a: Real,
+        b: Real,
+        c: Real,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Self {
+        let inertia_tensor = Matrix::from_diagonal(
+            mass / 5.0
+                * Vector::new(
+                    b.powi(2) + c.powi(2),
+                    a.powi(2) + c.powi(2),
+                    a.powi(2) + b.powi(2),
+                ),
+        );
+        RigidBody::new(mass, inertia_tensor, pulse, angular_momentum)
+    }
+
+    /// Creates a new body with an inertia tensor of a solid cylinder of radius `r` and `height`
+    /// along the Y axis.
+    pub fn new_cylinder(
+        mass: Real,
+        r: Real,
+        height: Real,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Self {
+        let lateral = mass * (3.0 * r.powi(2) + height.powi(2)) / 12.0;
+        let axial = mass * r.powi(2) / 2.0;
+        let inertia_tensor = Matrix::from_diagonal(Vector::new(lateral, axial, lateral));
+        RigidBody::new(mass, inertia_tensor, pulse, angular_momentum)
+    }
+
+    /// Creates a new body with an inertia tensor of a solid capsule: a cylinder of radius `r` and
+    /// height `depth` along the Y axis, capped with two hemispheres of radius `r`.
+    pub fn new_capsule(
+        mass: Real,
+        r: Real,
+        depth: Real,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Self {
+        // Mass is split between the cylinder and the hemispheres by their volumes
+        let cylinder_volume = depth;
+        let hemispheres_volume = 4.0 / 3.0 * r;
+        let cylinder_mass = mass * cylinder_volume / (cylinder_volume + hemispheres_volume);
+        let hemispheres_mass = mass - cylinder_mass;
+
+        let lateral = cylinder_mass * (r.powi(2) / 4.0 + depth.powi(2) / 12.0)
+            + hemispheres_mass
+                * (2.0 * r.powi(2) / 5.0 + depth.powi(2) / 4.0 + 3.0 * depth * r / 8.0);
+        let axial = cylinder_mass * r.powi(2) / 2.0 + hemispheres_mass * 2.0 * r.powi(2) / 5.0;
+        let inertia_tensor = Matrix::from_diagonal(Vector::new(lateral, axial, lateral));
         RigidBody::new(mass, inertia_tensor, pulse, angular_momentum)
     }
+
+    /// Creates a new body with an inertia tensor of a solid cone with base radius `r` and `height`
+    /// along the Y axis.
+    ///
+    /// The origin of the body is its center of mass, which lies on the axis at a quarter of the
+    /// `height` above the base.
+    pub fn new_cone(
+        mass: Real,
+        r: Real,
+        height: Real,
+        pulse: Vector,
+        angular_momentum: Vector,
+    ) -> Self {
+        let lateral = mass * (3.0 * r.powi(2) / 20.0 + 3.0 * height.powi(2) / 80.0);
+        let axial = 3.0 / 10.0 * mass * r.powi(2);
+        let inertia_tensor = Matrix::from_diagonal(Vector::new(lateral, axial, lateral));
+        RigidBody::new(mass, inertia_tensor, pulse, angular_momentum)
+    }
+
+    /// Creates a new body with an inertia tensor of a thin rod of `length` along the Y axis.
+    ///
+    /// Inertia of the rod around its own axis is zero, so the rod never spins around it.
+    pub fn new_rod(mass: Real, length: Real, pulse: Vector, angular_momentum: Vector) -> Self {
+        let lateral = mass * length.powi(2) / 12.0;
+        let mut rod = RigidBody::new(mass, Matrix::IDENTITY, pulse, angular_momentum);
+        rod.intertia_tensor_body = Matrix::from_diagonal(Vector::new(lateral, 0.0, lateral));
+        // The tensor is singular, so it is inverted only on the plane orthogonal to the axis
+        rod.intertia_tensor_body_inv =
+            Matrix::from_diagonal(Vector::new(1.0 / lateral, 0.0, 1.0 / lateral));
+        rod
+    }
 }
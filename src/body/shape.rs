@@ -0,0 +1,161 @@
+use bevy::render::{
+    mesh::{Indices, Mesh},
+    render_resource::PrimitiveTopology,
+};
+
+/// A solid cone with the axis along Y.
+///
+/// The mesh is centered at the center of mass of the cone: the base lies at a quarter of the
+/// `height` below the origin and the apex at three quarters above it.
+#[derive(Clone, Copy, Debug)]
+pub struct Cone {
+    /// Radius of the base in the XZ plane.
+    pub radius: f32,
+    /// Height of the cone in the Y axis.
+    pub height: f32,
+    /// The number of vertices around the base.
+    pub resolution: u32,
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            height: 1.0,
+            resolution: 32,
+        }
+    }
+}
+
+impl From<Cone> for Mesh {
+    fn from(c: Cone) -> Self {
+        debug_assert!(c.radius > 0.0);
+        debug_assert!(c.height > 0.0);
+        debug_assert!(c.resolution > 2);
+
+        let base_y = -c.height / 4.0;
+        let apex_y = 3.0 * c.height / 4.0;
+        let step_theta = std::f32::consts::TAU / c.resolution as f32;
+        // Normals of the side are tilted up by the slope of the cone
+        let normal_length = (c.height.powi(2) + c.radius.powi(2)).sqrt();
+        let normal_xz = c.height / normal_length;
+        let normal_y = c.radius / normal_length;
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = Vec::new();
+
+        // side: every segment gets its own apex vertex, so normals are smooth around the axis
+        for segment in 0..=c.resolution {
+            let theta = segment as f32 * step_theta;
+            let (sin, cos) = theta.sin_cos();
+            let u = segment as f32 / c.resolution as f32;
+            positions.push([c.radius * cos, base_y, c.radius * sin]);
+            normals.push([normal_xz * cos, normal_y, normal_xz * sin]);
+            uvs.push([u, 0.0]);
+
+            let (sin, cos) = (theta + step_theta / 2.0).sin_cos();
+            positions.push([0.0, apex_y, 0.0]);
+            normals.push([normal_xz * cos, normal_y, normal_xz * sin]);
+            uvs.push([u, 1.0]);
+        }
+        for segment in 0..c.resolution {
+            let base = 2 * segment;
+            indices.extend_from_slice(&[base, base + 1, base + 2]);
+        }
+
+        // base
+        let offset = positions.len() as u32;
+        for segment in 0..c.resolution {
+            let theta = segment as f32 * step_theta;
+            let (sin, cos) = theta.sin_cos();
+            positions.push([c.radius * cos, base_y, c.radius * sin]);
+            normals.push([0.0, -1.0, 0.0]);
+            uvs.push([0.5 * (cos + 1.0), 1.0 - 0.5 * (sin + 1.0)]);
+        }
+        for i in 1..(c.resolution - 1) {
+            indices.extend_from_slice(&[offset, offset + i, offset + i + 1]);
+        }
+
+        Mesh::new(PrimitiveTopology::TriangleList)
+            .with_indices(Some(Indices::U32(indices)))
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    }
+}
+
+/// A solid ellipsoid with semi-axes `a`, `b` and `c` along the X, Y and Z axes.
+#[derive(Clone, Copy, Debug)]
+pub struct Ellipsoid {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    /// Longitudinal sectors
+    pub sectors: u32,
+    /// Latitudinal stacks
+    pub stacks: u32,
+}
+
+impl Default for Ellipsoid {
+    fn default() -> Self {
+        Self {
+            a: 1.0,
+            b: 0.5,
+            c: 0.5,
+            sectors: 36,
+            stacks: 18,
+        }
+    }
+}
+
+impl From<Ellipsoid> for Mesh {
+    fn from(e: Ellipsoid) -> Self {
+        debug_assert!(e.a > 0.0 && e.b > 0.0 && e.c > 0.0);
+        debug_assert!(e.sectors > 2 && e.stacks > 1);
+
+        let sector_step = std::f32::consts::TAU / e.sectors as f32;
+        let stack_step = std::f32::consts::PI / e.stacks as f32;
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = Vec::new();
+
+        for i in 0..=e.stacks {
+            // from the top pole to the bottom one
+            let (sin_phi, cos_phi) = (i as f32 * stack_step).sin_cos();
+            for j in 0..=e.sectors {
+                let (sin_theta, cos_theta) = (j as f32 * sector_step).sin_cos();
+                let unit = [sin_phi * cos_theta, cos_phi, sin_phi * sin_theta];
+                positions.push([e.a * unit[0], e.b * unit[1], e.c * unit[2]]);
+                // Gradient of the implicit equation of the ellipsoid
+                let normal = [unit[0] / e.a, unit[1] / e.b, unit[2] / e.c];
+                let length = normal.iter().map(|n| n * n).sum::<f32>().sqrt();
+                normals.push(normal.map(|n| n / length));
+                uvs.push([j as f32 / e.sectors as f32, i as f32 / e.stacks as f32]);
+            }
+        }
+
+        let row = e.sectors + 1;
+        for i in 0..e.stacks {
+            for j in 0..e.sectors {
+                let k1 = i * row + j;
+                let k2 = k1 + row;
+                if i != 0 {
+                    indices.extend_from_slice(&[k1, k1 + 1, k2]);
+                }
+                if i != e.stacks - 1 {
+                    indices.extend_from_slice(&[k1 + 1, k2 + 1, k2]);
+                }
+            }
+        }
+
+        Mesh::new(PrimitiveTopology::TriangleList)
+            .with_indices(Some(Indices::U32(indices)))
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::{
+    math::{to_f64, Real},
+    settings::SettingsResource,
+};
+
+/// Simulated time.
+///
+/// The simulation advances by a fixed step every `FixedUpdate`, so results do not depend on the
+/// render frame rate. All pdrust systems take time from this resource.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct SimulationClock {
+    elapsed: f64,
+    delta: Real,
+    steps: u64,
+}
+
+impl SimulationClock {
+    /// Simulated time elapsed since the start of the simulation
+    pub fn elapsed_seconds(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Length of the current step in simulated time.
+    /// It equals to the fixed timestep divided by the slow motion coefficient.
+    pub fn delta_seconds(&self) -> Real {
+        self.delta
+    }
+
+    /// Number of steps made since the start of the simulation
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Advances the clock by a step of length `delta`.
+    pub fn advance(&mut self, delta: Real) {
+        self.delta = delta;
+        self.elapsed += to_f64(delta);
+        self.steps += 1;
+    }
+}
+
+/// Advances [`SimulationClock`] by the fixed timestep, taking slow motion into account.
+pub fn advance_simulation_clock(
+    mut clock: ResMut<SimulationClock>,
+    time: Res<Time<Fixed>>,
+    settings: Res<SettingsResource>,
+) {
+    let timestep = time.timestep().as_secs_f64() as Real;
+    clock.advance(timestep / settings.slow_motion_koef as Real);
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::math::{Real, Vector};
+
 use super::DistanceConstraint;
 
 #[derive(Bundle)]
@@ -15,10 +17,10 @@ impl DistanceConstraintBundle {
         material: Handle<StandardMaterial>,
         first_body: Entity,
         second_body: Entity,
-        first_body_offset: Vec3,
-        second_body_offset: Vec3,
-        min_distance: f32,
-        max_distance: f32,
+        first_body_offset: Vector,
+        second_body_offset: Vector,
+        min_distance: Real,
+        max_distance: Real,
     ) -> Entity {
         commands
             .spawn(DistanceConstraintBundle {
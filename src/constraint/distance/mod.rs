@@ -1,26 +1,28 @@
 use bevy::prelude::*;
 
+use crate::math::{Real, Vector};
+
 pub mod bundle;
 pub mod system;
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct DistanceConstraint {
     first_body: Entity,
     second_body: Entity,
-    first_body_offset: Vec3,
-    second_body_offset: Vec3,
-    min_distance: f32,
-    max_distance: f32,
+    first_body_offset: Vector,
+    second_body_offset: Vector,
+    min_distance: Real,
+    max_distance: Real,
 }
 
 impl DistanceConstraint {
     pub fn new(
         first_body: Entity,
         second_body: Entity,
-        first_body_offset: Vec3,
-        second_body_offset: Vec3,
-        min_distance: f32,
-        max_distance: f32,
+        first_body_offset: Vector,
+        second_body_offset: Vector,
+        min_distance: Real,
+        max_distance: Real,
     ) -> Self {
         return Self {
             first_body,
@@ -31,4 +33,9 @@ impl DistanceConstraint {
             max_distance,
         };
     }
+
+    /// Returns entities of the first and the second constrained bodies.
+    pub fn bodies(&self) -> (Entity, Entity) {
+        (self.first_body, self.second_body)
+    }
 }
@@ -1,110 +1,96 @@
 use bevy::prelude::*;
 
 use crate::{
-    body::{Body, RigidBody},
-    settings::SettingsResource,
+    body::{Body, PhysicsTransform, RigidBody},
+    math::{to_f32, to_vec3, Matrix, Real},
+    solver::state::BodyState,
 };
 
 use super::DistanceConstraint;
 
-/// Solves all distances constraints.
-/// See [Physics Tutorial 3 - Constraints](https://research.ncl.ac.uk/game/mastersdegree/gametechnologies/previousinformation/physics3constraints)
-/// from New Castle University for detailed explanation of what is going on here
-pub fn solve_distance_constraints(
-    constraints: Query<&DistanceConstraint>,
-    mut bodies_query: Query<
-        (&mut Body, &mut Transform, Option<&mut RigidBody>),
-        Without<DistanceConstraint>,
-    >,
-    time: Res<Time>,
-    settings: Res<SettingsResource>,
-) {
-    let dt = time.delta_seconds() / settings.slow_motion_koef;
-    let constraint_dt = dt / settings.constraints_substeps as f32;
-    for _ in 0..settings.constraints_substeps {
-        for constraint in &constraints {
-            let [(_b1, t1, rb1), (_b2, t2, rb2)] = bodies_query
-                .get_many_mut([constraint.first_body, constraint.second_body])
-                .unwrap();
-
-            let x1 = Body.body_to_world_coordinates(constraint.first_body_offset, &t1);
-            let x2 = Body.body_to_world_coordinates(constraint.second_body_offset, &t2);
-
-            let v1 = rb1
+impl DistanceConstraint {
+    /// Applies impulses to `first` and `second` bodies so that the distance between them stays
+    /// in `[min_distance; max_distance]` range.
+    /// See [Physics Tutorial 3 - Constraints](https://research.ncl.ac.uk/game/mastersdegree/gametechnologies/previousinformation/physics3constraints)
+    /// from New Castle University for detailed explanation of what is going on here
+    pub fn solve(
+        &self,
+        first: &mut BodyState,
+        second: &mut BodyState,
+        baumgarte_constant: Real,
+        constraint_dt: Real,
+    ) {
+        // Kinematic bodies have velocities too
+        let (v1, omega1) = (first.get_velocity(), first.get_angular_velocity());
+        let (v2, omega2) = (second.get_velocity(), second.get_angular_velocity());
+
+        let (t1, rb1) = (&first.transform, first.rigid_body.as_mut());
+        let (t2, rb2) = (&second.transform, second.rigid_body.as_mut());
+
+        let x1 = Body.body_to_world_coordinates(self.first_body_offset, t1);
+        let x2 = Body.body_to_world_coordinates(self.second_body_offset, t2);
+
+        let m1_inversed = rb1.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
+        let m2_inversed = rb2.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
+
+        let i1_inversed = rb1
+            .as_ref()
+            .map(|b| b.get_inertia_tensor_inv(t1))
+            .unwrap_or_else(|| Matrix::ZERO);
+        let i2_inversed = rb2
+            .as_ref()
+            .map(|b| b.get_inertia_tensor_inv(t2))
+            .unwrap_or_else(|| Matrix::ZERO);
+
+        let r1 = x1
+            - rb1
                 .as_ref()
-                .map(|b| b.get_velocity())
-                .unwrap_or_else(|| Vec3::ZERO);
-            let v2 = rb2
+                .map(|b| b.get_center_of_mass(t1))
+                .unwrap_or(t1.translation);
+        let r2 = x2
+            - rb2
                 .as_ref()
-                .map(|b| b.get_velocity())
-                .unwrap_or_else(|| Vec3::ZERO);
+                .map(|b| b.get_center_of_mass(t2))
+                .unwrap_or(t2.translation);
 
-            let omega1 = rb1
-                .as_ref()
-                .map(|b| b.get_angular_velocity(&t1))
-                .unwrap_or_else(|| Vec3::ZERO);
-            let omega2 = rb2
-                .as_ref()
-                .map(|b| b.get_angular_velocity(&t2))
-                .unwrap_or_else(|| Vec3::ZERO);
+        let ab = x2 - x1;
+        let abn = ab.normalize();
 
-            let m1_inversed = rb1.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
-            let m2_inversed = rb2.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
+        let current_distance = ab.length();
+        if self.min_distance <= current_distance && current_distance <= self.max_distance {
+            return;
+        }
 
-            let i1_inversed = rb1
-                .as_ref()
-                .map(|b| b.get_inertia_tensor_inv(&t1))
-                .unwrap_or_else(|| Mat3::ZERO);
-            let i2_inversed = rb2
-                .as_ref()
-                .map(|b| b.get_inertia_tensor_inv(&t2))
-                .unwrap_or_else(|| Mat3::ZERO);
-
-            let r1 = x1 - t1.translation;
-            let r2 = x2 - t2.translation;
-
-            let ab = x2 - x1;
-            let abn = ab.normalize();
-
-            let current_distance = ab.length();
-            if constraint.min_distance <= current_distance
-                && current_distance <= constraint.max_distance
-            {
-                continue;
-            }
-
-            let target_distance = if current_distance < constraint.min_distance {
-                constraint.min_distance
-            } else {
-                constraint.max_distance
-            };
-
-            let j1 = -abn;
-            let j2 = -r1.cross(abn);
-            let j3 = abn;
-            let j4 = r2.cross(abn);
-
-            let constraint_mass = m1_inversed * j1.length_squared()
-                + j2.dot(i1_inversed * j2)
-                + m2_inversed * j3.length_squared()
-                + j4.dot(i2_inversed * j4);
-
-            let jv = j1.dot(v1) + j2.dot(omega1) + j3.dot(v2) + j4.dot(omega2);
-
-            let distance_offset = current_distance - target_distance;
-            let baumgarte_constant = settings.baumgarte_constant;
-            let b = (baumgarte_constant / constraint_dt) * distance_offset;
-
-            let lambda = -(jv + b) / constraint_mass;
-
-            if let Some(mut body) = rb1 {
-                body.pulse += lambda * j1;
-                body.angular_momentum += lambda * j2;
-            }
-            if let Some(mut body) = rb2 {
-                body.pulse += lambda * j3;
-                body.angular_momentum += lambda * j4;
-            }
+        let target_distance = if current_distance < self.min_distance {
+            self.min_distance
+        } else {
+            self.max_distance
+        };
+
+        let j1 = -abn;
+        let j2 = -r1.cross(abn);
+        let j3 = abn;
+        let j4 = r2.cross(abn);
+
+        let constraint_mass = m1_inversed * j1.length_squared()
+            + j2.dot(i1_inversed * j2)
+            + m2_inversed * j3.length_squared()
+            + j4.dot(i2_inversed * j4);
+
+        let jv = j1.dot(v1) + j2.dot(omega1) + j3.dot(v2) + j4.dot(omega2);
+
+        let distance_offset = current_distance - target_distance;
+        let b = (baumgarte_constant / constraint_dt) * distance_offset;
+
+        let lambda = -(jv + b) / constraint_mass;
+
+        if let Some(body) = rb1 {
+            body.pulse += lambda * j1;
+            body.angular_momentum += lambda * j2;
+        }
+        if let Some(body) = rb2 {
+            body.pulse += lambda * j3;
+            body.angular_momentum += lambda * j4;
         }
     }
 }
@@ -121,15 +107,22 @@ pub fn update_distance_constraints_transformation(
             .get_many_mut([constraint.first_body, constraint.second_body])
             .unwrap();
 
-        let x1 = Body.body_to_world_coordinates(constraint.first_body_offset, &t1);
-        let x2 = Body.body_to_world_coordinates(constraint.second_body_offset, &t2);
+        let x1 =
+            to_vec3(Body.body_to_world_coordinates(
+                constraint.first_body_offset,
+                &PhysicsTransform::from(*t1),
+            ));
+        let x2 = to_vec3(Body.body_to_world_coordinates(
+            constraint.second_body_offset,
+            &PhysicsTransform::from(*t2),
+        ));
 
         let constraint_length_vec = (x1) - (x2);
         let constarint_mid = (x1 + x2) / 2.0;
 
         // Cylinder diameter
         // Constraint is rendred as a cylinder,
-        transform.scale.y = constraint.max_distance;
+        transform.scale.y = to_f32(constraint.max_distance);
         transform.scale.x = 1.0;
         transform.scale.z = transform.scale.x;
         // translation is a position of cylinder center
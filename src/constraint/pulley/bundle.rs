@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::math::{to_vec3, Real, Vector};
+
 use super::PulleyConstraint;
 
 #[derive(Bundle)]
@@ -20,9 +22,9 @@ impl PulleyBundle {
     fn new(
         first_body: Entity,
         second_body: Entity,
-        first_body_offset: Vec3,
-        second_body_offset: Vec3,
-        max_distance: f32,
+        first_body_offset: Vector,
+        second_body_offset: Vector,
+        max_distance: Real,
         pbr: PbrBundle,
         render: PulleyRender,
     ) -> Self {
@@ -47,10 +49,10 @@ impl PulleyBundle {
         pulley_material: Handle<StandardMaterial>,
         first_body: Entity,
         second_body: Entity,
-        first_body_offset: Vec3,
-        second_body_offset: Vec3,
-        max_distance: f32,
-        pulley_position: Vec3,
+        first_body_offset: Vector,
+        second_body_offset: Vector,
+        max_distance: Real,
+        pulley_position: Vector,
     ) -> Entity {
         let thread_radius = 0.1;
         let first_thread_id = commands
@@ -81,7 +83,7 @@ impl PulleyBundle {
                 radius: 2.0 * thread_radius,
                 ..default()
             })),
-            transform: Transform::from_translation(pulley_position),
+            transform: Transform::from_translation(to_vec3(pulley_position)),
             material: pulley_material,
             ..default()
         };
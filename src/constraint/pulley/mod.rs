@@ -1,24 +1,26 @@
 use bevy::prelude::*;
 
+use crate::math::{Real, Vector};
+
 pub mod bundle;
 pub mod system;
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub struct PulleyConstraint {
     first_body: Entity,
     second_body: Entity,
-    first_body_offset: Vec3,
-    second_body_offset: Vec3,
-    max_distance: f32,
+    first_body_offset: Vector,
+    second_body_offset: Vector,
+    max_distance: Real,
 }
 
 impl PulleyConstraint {
     pub fn new(
         first_body: Entity,
         second_body: Entity,
-        first_body_offset: Vec3,
-        second_body_offset: Vec3,
-        max_distance: f32,
+        first_body_offset: Vector,
+        second_body_offset: Vector,
+        max_distance: Real,
     ) -> Self {
         Self {
             first_body,
@@ -28,4 +30,9 @@ impl PulleyConstraint {
             max_distance,
         }
     }
+
+    /// Returns entities of the first and the second constrained bodies.
+    pub fn bodies(&self) -> (Entity, Entity) {
+        (self.first_body, self.second_body)
+    }
 }
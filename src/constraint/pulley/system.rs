@@ -1,103 +1,91 @@
 use bevy::prelude::*;
 
 use crate::{
-    body::{Body, RigidBody},
-    settings::SettingsResource,
+    body::{Body, PhysicsTransform},
+    math::{to_vec3, Matrix, Real, Vector},
+    solver::state::BodyState,
 };
 
 use super::{bundle::PulleyRender, PulleyConstraint};
 
-pub fn solve_pulley_constraints(
-    constraints: Query<(&PulleyConstraint, &Transform)>,
-    mut bodies_query: Query<
-        (&mut Body, &mut Transform, Option<&mut RigidBody>),
-        Without<PulleyConstraint>,
-    >,
-    time: Res<Time>,
-    settings: Res<SettingsResource>,
-) {
-    let dt = time.delta_seconds() / settings.slow_motion_koef;
-    let constraint_dt = dt / settings.constraints_substeps as f32;
-    for _ in 0..settings.constraints_substeps {
-        for (constraint, pulley_transform) in &constraints {
-            let [(_b1, t1, rb1), (_b2, t2, rb2)] = bodies_query
-                .get_many_mut([constraint.first_body, constraint.second_body])
-                .unwrap();
-
-            let pulley_position = pulley_transform.translation;
-
-            let x1 = Body.body_to_world_coordinates(constraint.first_body_offset, &t1);
-            let x2 = Body.body_to_world_coordinates(constraint.second_body_offset, &t2);
-
-            let v1 = rb1
-                .as_ref()
-                .map(|b| b.get_velocity())
-                .unwrap_or_else(|| Vec3::ZERO);
-            let v2 = rb2
-                .as_ref()
-                .map(|b| b.get_velocity())
-                .unwrap_or_else(|| Vec3::ZERO);
-
-            let omega1 = rb1
+impl PulleyConstraint {
+    /// Applies impulses to `first` and `second` bodies so that the sum of distances from them to
+    /// the pulley at `pulley_position` does not exceed `max_distance`.
+    pub fn solve(
+        &self,
+        first: &mut BodyState,
+        second: &mut BodyState,
+        pulley_position: Vector,
+        baumgarte_constant: Real,
+        constraint_dt: Real,
+    ) {
+        // Kinematic bodies have velocities too
+        let (v1, omega1) = (first.get_velocity(), first.get_angular_velocity());
+        let (v2, omega2) = (second.get_velocity(), second.get_angular_velocity());
+
+        let (t1, rb1) = (&first.transform, first.rigid_body.as_mut());
+        let (t2, rb2) = (&second.transform, second.rigid_body.as_mut());
+
+        let x1 = Body.body_to_world_coordinates(self.first_body_offset, t1);
+        let x2 = Body.body_to_world_coordinates(self.second_body_offset, t2);
+
+        let m1_inversed = rb1.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
+        let m2_inversed = rb2.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
+
+        let i1_inversed = rb1
+            .as_ref()
+            .map(|b| b.get_inertia_tensor_inv(t1))
+            .unwrap_or_else(|| Matrix::ZERO);
+        let i2_inversed = rb2
+            .as_ref()
+            .map(|b| b.get_inertia_tensor_inv(t2))
+            .unwrap_or_else(|| Matrix::ZERO);
+
+        let r1 = x1
+            - rb1
                 .as_ref()
-                .map(|b| b.get_angular_velocity(&t1))
-                .unwrap_or_else(|| Vec3::ZERO);
-            let omega2 = rb2
+                .map(|b| b.get_center_of_mass(t1))
+                .unwrap_or(t1.translation);
+        let r2 = x2
+            - rb2
                 .as_ref()
-                .map(|b| b.get_angular_velocity(&t2))
-                .unwrap_or_else(|| Vec3::ZERO);
+                .map(|b| b.get_center_of_mass(t2))
+                .unwrap_or(t2.translation);
 
-            let m1_inversed = rb1.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
-            let m2_inversed = rb2.as_ref().map(|b| 1.0 / b.mass).unwrap_or_else(|| 0.0);
+        let d1 = x1 - pulley_position;
+        let d2 = x2 - pulley_position;
 
-            let i1_inversed = rb1
-                .as_ref()
-                .map(|b| b.get_inertia_tensor_inv(&t1))
-                .unwrap_or_else(|| Mat3::ZERO);
-            let i2_inversed = rb2
-                .as_ref()
-                .map(|b| b.get_inertia_tensor_inv(&t2))
-                .unwrap_or_else(|| Mat3::ZERO);
-
-            let r1 = x1 - t1.translation;
-            let r2 = x2 - t2.translation;
-
-            let d1 = x1 - pulley_position;
-            let d2 = x2 - pulley_position;
+        let target_distance = self.max_distance;
 
-            let target_distance = constraint.max_distance;
-
-            let current_distance = d1.length() + d2.length();
-            let distance_offset = current_distance - target_distance;
-            if distance_offset <= 0.0 {
-                continue;
-            }
+        let current_distance = d1.length() + d2.length();
+        let distance_offset = current_distance - target_distance;
+        if distance_offset <= 0.0 {
+            return;
+        }
 
-            let j1 = d1;
-            let j2 = r1.cross(d1);
-            let j3 = d2;
-            let j4 = r2.cross(d2);
+        let j1 = d1;
+        let j2 = r1.cross(d1);
+        let j3 = d2;
+        let j4 = r2.cross(d2);
 
-            let constraint_mass = m1_inversed * j1.dot(j1)
-                + j2.dot(i1_inversed * j2)
-                + m2_inversed * j3.dot(j3)
-                + j4.dot(i2_inversed * j4);
+        let constraint_mass = m1_inversed * j1.dot(j1)
+            + j2.dot(i1_inversed * j2)
+            + m2_inversed * j3.dot(j3)
+            + j4.dot(i2_inversed * j4);
 
-            let jv = j1.dot(v1) + j2.dot(omega1) + j3.dot(v2) + j4.dot(omega2);
+        let jv = j1.dot(v1) + j2.dot(omega1) + j3.dot(v2) + j4.dot(omega2);
 
-            let baumgarte_constant = settings.baumgarte_constant;
-            let b = (baumgarte_constant / constraint_dt) * distance_offset;
+        let b = (baumgarte_constant / constraint_dt) * distance_offset;
 
-            let lambda = -(jv + b) / constraint_mass;
+        let lambda = -(jv + b) / constraint_mass;
 
-            if let Some(mut body) = rb1 {
-                body.pulse += lambda * j1;
-                body.angular_momentum += lambda * j2;
-            }
-            if let Some(mut body) = rb2 {
-                body.pulse += lambda * j3;
-                body.angular_momentum += lambda * j4;
-            }
+        if let Some(body) = rb1 {
+            body.pulse += lambda * j1;
+            body.angular_momentum += lambda * j2;
+        }
+        if let Some(body) = rb2 {
+            body.pulse += lambda * j3;
+            body.angular_momentum += lambda * j4;
         }
     }
 }
@@ -116,8 +104,15 @@ pub fn update_pulley_constraints_transformation(
             ])
             .unwrap();
 
-        let x1 = Body.body_to_world_coordinates(constraint.first_body_offset, &t1);
-        let x2 = Body.body_to_world_coordinates(constraint.second_body_offset, &t2);
+        let x1 =
+            to_vec3(Body.body_to_world_coordinates(
+                constraint.first_body_offset,
+                &PhysicsTransform::from(*t1),
+            ));
+        let x2 = to_vec3(Body.body_to_world_coordinates(
+            constraint.second_body_offset,
+            &PhysicsTransform::from(*t2),
+        ));
         let p = pulley_transform.translation;
 
         c1.translation = (p + x1) / 2.0;
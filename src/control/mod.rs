@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+/// Controls whether the simulation is running.
+///
+/// All systems of [`crate::PDRustPlugin`] belong to [`SimulationSet`], which runs only when the
+/// simulation is [`SimulationState::Running`] or has steps left in [`SimulationState::Stepping`].
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SimulationState {
+    #[default]
+    Running,
+    Paused,
+    /// Makes the given number of `FixedUpdate` steps and then pauses
+    Stepping(usize),
+    /// Plays the recorded [`crate::history::SimulationHistory`] backwards, one step per
+    /// `FixedUpdate`, and pauses at the oldest recorded step
+    Rewinding,
+}
+
+impl SimulationState {
+    pub fn pause(&mut self) {
+        *self = SimulationState::Paused;
+    }
+
+    pub fn resume(&mut self) {
+        *self = SimulationState::Running;
+    }
+
+    pub fn rewind(&mut self) {
+        *self = SimulationState::Rewinding;
+    }
+
+    pub fn is_running(&self) -> bool {
+        match self {
+            SimulationState::Running => true,
+            SimulationState::Paused | SimulationState::Rewinding => false,
+            SimulationState::Stepping(steps) => *steps > 0,
+        }
+    }
+}
+
+/// Requests the given number of `FixedUpdate` steps.
+/// A running simulation is paused after these steps, a stepping one gets additional steps.
+/// Requests of zero steps are ignored.
+#[derive(Event, Clone, Copy)]
+pub struct StepSimulation(pub usize);
+
+/// Systems of [`crate::PDRustPlugin`] that advance the simulation.
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SimulationSet;
+
+/// Run condition of [`SimulationSet`].
+pub fn simulation_is_running(state: Res<SimulationState>) -> bool {
+    state.is_running()
+}
+
+/// Handles [`StepSimulation`] events.
+pub fn handle_step_requests(
+    mut events: EventReader<StepSimulation>,
+    mut state: ResMut<SimulationState>,
+) {
+    for StepSimulation(steps) in events.read() {
+        if *steps == 0 {
+            continue;
+        }
+        *state = match *state {
+            SimulationState::Stepping(left) => SimulationState::Stepping(left + steps),
+            _ => SimulationState::Stepping(*steps),
+        };
+    }
+}
+
+/// Counts steps made in [`SimulationState::Stepping`] and pauses the simulation after the last one.
+pub fn count_simulation_step(mut state: ResMut<SimulationState>) {
+    if let SimulationState::Stepping(left) = *state {
+        *state = if left > 1 {
+            SimulationState::Stepping(left - 1)
+        } else {
+            SimulationState::Paused
+        };
+    }
+}
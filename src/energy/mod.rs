@@ -1,15 +1,18 @@
 use bevy::prelude::*;
 
 use crate::{
-    body::{Body, RigidBody},
+    body::{Body, BodyType, PhysicsTransform, RigidBody},
+    history::HistoryRestored,
+    math::{Real, Vector},
     settings::SettingsResource,
+    solver::state::PhysicsState,
     springs::Spring,
 };
 
 /// Something that has an energy that can be computed.
 #[derive(Component)]
 pub struct Energy {
-    energy: f32,
+    energy: Real,
 }
 
 impl Default for Energy {
@@ -19,21 +22,137 @@ impl Default for Energy {
 }
 
 impl Energy {
-    pub fn get_energy(&self) -> f32 {
+    pub fn get_energy(&self) -> Real {
         return self.energy;
     }
 }
 
+/// Keeps total energy of a simulation at its initial value by rescaling velocities after every
+/// `FixedUpdate`, see [`SettingsResource::energy_projection`].
+///
+/// Scaling pulses and angular momenta of all bodies by the same factor changes only kinetic
+/// energy and keeps velocity constraints satisfied. The projection is skipped while any spring
+/// or body has damping or drag, a kinematic body moves or an external force acts, since then
+/// energy is not supposed to be conserved. The initial energy is taken again after that.
+#[derive(Resource, Default)]
+pub struct EnergyProjection {
+    initial_energy: Option<Real>,
+    correction: Option<Real>,
+}
+
+impl EnergyProjection {
+    /// Energy the simulation is projected to. It is taken before the first projected step after
+    /// [`EnergyProjection::reset`].
+    pub fn initial_energy(&self) -> Option<Real> {
+        self.initial_energy
+    }
+
+    /// Energy added (or removed, if negative) by the last projection.
+    /// `None` if the projection was not applied.
+    pub fn correction(&self) -> Option<Real> {
+        self.correction
+    }
+
+    /// Forgets the initial energy, so it is taken again before the next projected step.
+    /// Called when the simulation changes outside of a step, e.g. bodies are added or the
+    /// history is restored.
+    pub fn reset(&mut self) {
+        self.initial_energy = None;
+        self.correction = None;
+    }
+
+    /// Takes energy of the `state` as the initial one, unless it has already been taken.
+    /// Called before a step.
+    pub fn start(&mut self, state: &PhysicsState) {
+        if self.initial_energy.is_none() {
+            self.initial_energy = Some(state.compute_energy());
+        }
+    }
+
+    /// Rescales velocities of bodies of the `state` so that its energy equals the initial one.
+    /// Called after a step.
+    pub fn apply(&mut self, state: &mut PhysicsState) {
+        self.correction = None;
+        if !conserves_energy(state) {
+            self.reset();
+            return;
+        }
+        let Some(initial_energy) = self.initial_energy else {
+            return;
+        };
+        let energy = state.compute_energy();
+        let kinetic_energy = state.compute_kinetic_energy();
+        let target_kinetic_energy = kinetic_energy + initial_energy - energy;
+        if kinetic_energy <= 0.0 || target_kinetic_energy < 0.0 {
+            // Potential energy alone is above the initial energy, velocities can not fix it
+            self.correction = Some(0.0);
+            return;
+        }
+        let scale = (target_kinetic_energy / kinetic_energy).sqrt();
+        for body in state
+            .bodies
+            .iter_mut()
+            .filter_map(|s| s.rigid_body.as_mut())
+        {
+            body.pulse *= scale;
+            body.angular_momentum *= scale;
+        }
+        self.correction = Some(initial_energy - energy);
+    }
+}
+
+/// Whether nothing adds energy to the `state` or takes it away: there is no damping or drag,
+/// no moving kinematic bodies and no external forces.
+fn conserves_energy(state: &PhysicsState) -> bool {
+    let damped_springs = state
+        .springs
+        .iter()
+        .any(|s| s.spring.damping_constant != 0.0);
+    let driven_bodies = state.bodies.iter().any(|s| {
+        let moving = match s.body_type {
+            BodyType::Kinematic {
+                velocity,
+                angular_velocity,
+            } => velocity != Vector::ZERO || angular_velocity != Vector::ZERO,
+            _ => false,
+        };
+        moving
+            || s.external_force != Vector::ZERO
+            || s.external_torque != Vector::ZERO
+            || s.rigid_body.is_some_and(|b| b.is_damped())
+    });
+    !damped_springs && !driven_bodies
+}
+
+/// Resets [`EnergyProjection`] when bodies or springs are added or removed, or the history is
+/// restored, since the initial energy does not correspond to the simulation anymore.
+#[allow(clippy::type_complexity)]
+pub fn reset_energy_projection(
+    mut energy_projection: ResMut<EnergyProjection>,
+    mut restored: EventReader<HistoryRestored>,
+    added: Query<(), Or<(Added<Body>, Added<Spring>)>>,
+    mut removed_bodies: RemovedComponents<Body>,
+    mut removed_springs: RemovedComponents<Spring>,
+) {
+    let restored = restored.read().count() > 0;
+    let removed = removed_bodies.read().count() + removed_springs.read().count() > 0;
+    if restored || removed || !added.is_empty() {
+        energy_projection.reset();
+    }
+}
+
 pub fn update_energy_for_springs(
     mut springs_query: Query<(&Spring, &mut Energy)>,
-    bodies_query: Query<(Entity, &Body, &Transform), Without<Spring>>,
+    bodies_query: Query<(Entity, &Body, &Transform, Option<&PhysicsTransform>), Without<Spring>>,
 ) {
     for (spring, mut spring_energy) in springs_query.iter_mut() {
-        let [(_, b1, t1), (_, b2, t2)] = bodies_query
+        let [(_, b1, t1, pt1), (_, b2, t2, pt2)] = bodies_query
             .get_many([spring.first_body, spring.second_body])
             .unwrap();
-        let sp1 = b1.body_to_world_coordinates(spring.first_body_attachment_point_offset, t1);
-        let sp2 = b2.body_to_world_coordinates(spring.second_body_attachment_point_offset, t2);
+        let t1 = PhysicsTransform::current(pt1, t1);
+        let t2 = PhysicsTransform::current(pt2, t2);
+        let sp1 = b1.body_to_world_coordinates(spring.first_body_attachment_point_offset, &t1);
+        let sp2 = b2.body_to_world_coordinates(spring.second_body_attachment_point_offset, &t2);
         let spring_length_vec = sp2 - sp1;
         let spring_length = spring_length_vec.length();
         let delta_length = spring_length - spring.rest_length;
@@ -44,10 +163,17 @@ pub fn update_energy_for_springs(
 }
 
 pub fn update_energy_for_rigid_bodies(
-    mut bodies_query: Query<(&RigidBody, &Body, &Transform, &mut Energy)>,
+    mut bodies_query: Query<(
+        &RigidBody,
+        &Body,
+        &Transform,
+        Option<&PhysicsTransform>,
+        &mut Energy,
+    )>,
     settings: Res<SettingsResource>,
 ) {
-    for (rb, _b, t, mut e) in bodies_query.iter_mut() {
-        e.energy = rb.compute_energy(t, settings.gravity_vector);
+    for (rb, _b, t, pt, mut e) in bodies_query.iter_mut() {
+        let t = PhysicsTransform::current(pt, t);
+        e.energy = rb.compute_energy(&t, settings.gravity_vector);
     }
 }
@@ -0,0 +1,281 @@
+use std::collections::VecDeque;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    body::{Body, PhysicsTransform, RigidBody},
+    clock::SimulationClock,
+    constraint::{distance::DistanceConstraint, pulley::PulleyConstraint},
+    control::SimulationState,
+    math::{Real, Vector},
+    springs::Spring,
+};
+
+/// State of a rigid body that changes during the simulation. Mass properties, damping and drag
+/// stay constant, so they are not recorded.
+#[derive(Clone, Copy)]
+struct BodySnapshot {
+    pulse: Vector,
+    angular_momentum: Vector,
+    sleeping: bool,
+    resting_time: Real,
+    dissipated_energy: Real,
+}
+
+impl BodySnapshot {
+    fn capture(body: &RigidBody) -> Self {
+        Self {
+            pulse: body.pulse,
+            angular_momentum: body.angular_momentum,
+            sleeping: body.sleeping,
+            resting_time: body.resting_time,
+            dissipated_energy: body.dissipated_energy,
+        }
+    }
+
+    fn restore(&self, body: &mut RigidBody) {
+        body.pulse = self.pulse;
+        body.angular_momentum = self.angular_momentum;
+        body.sleeping = self.sleeping;
+        body.resting_time = self.resting_time;
+        body.dissipated_energy = self.dissipated_energy;
+    }
+}
+
+/// State of the simulation at some step.
+#[derive(Clone)]
+struct Snapshot {
+    clock: SimulationClock,
+    bodies: Vec<(Entity, PhysicsTransform, Option<BodySnapshot>)>,
+    springs: Vec<(Entity, Spring)>,
+    distance_constraints: Vec<(Entity, DistanceConstraint)>,
+    pulley_constraints: Vec<(Entity, PulleyConstraint)>,
+}
+
+/// A ring buffer of recorded states of the simulation.
+///
+/// The state is recorded before every step, so the simulation can be scrubbed backwards and
+/// forwards with [`ScrubHistory`] or played backwards with [`SimulationState::Rewinding`].
+/// Resuming or stepping the simulation from a past state forgets the states after it.
+///
+/// Transforms, pulses, angular momenta, sleeping state and dissipated energy of bodies, springs,
+/// constraints and [`SimulationClock`] are recorded. Entities spawned after a recorded step keep
+/// their current state when it is restored.
+///
+/// Recording is disabled by default, since it copies the state of every body before every step.
+/// Set [`SimulationHistory::capacity`] to enable it.
+#[derive(Resource, Default)]
+pub struct SimulationHistory {
+    /// Maximum number of recorded steps, the oldest ones are forgotten. Zero (the default)
+    /// disables recording.
+    pub capacity: usize,
+    snapshots: VecDeque<Snapshot>,
+    /// Index of the restored snapshot, `None` if the simulation is at its latest state
+    cursor: Option<usize>,
+}
+
+impl SimulationHistory {
+    /// Creates a history that records up to `capacity` steps.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..default()
+        }
+    }
+
+    /// Number of recorded steps
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Index of the restored step among recorded ones, `None` if the simulation is at its latest
+    /// state.
+    pub fn position(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    /// Forgets all recorded steps.
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.cursor = None;
+    }
+
+    /// Records a snapshot as the latest one. Snapshots after the restored one are forgotten, and
+    /// a snapshot of the same step is replaced.
+    fn record(&mut self, snapshot: Snapshot) {
+        if let Some(cursor) = self.cursor.take() {
+            self.snapshots.truncate(cursor + 1);
+        }
+        if self.snapshots.back().map(|s| s.clock.steps()) == Some(snapshot.clock.steps()) {
+            self.snapshots.pop_back();
+        }
+        self.snapshots.push_back(snapshot);
+        while self.snapshots.len() > self.capacity {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Moves `offset` snapshots from the restored one (or from the latest one) and returns the
+    /// snapshot to restore.
+    fn seek(&mut self, offset: isize) -> Option<&Snapshot> {
+        let last = self.snapshots.len().checked_sub(1)?;
+        let target = self
+            .cursor
+            .unwrap_or(last)
+            .saturating_add_signed(offset)
+            .min(last);
+        self.cursor = Some(target);
+        self.snapshots.get(target)
+    }
+}
+
+/// Moves through [`SimulationHistory`] by the given number of steps (negative values go back)
+/// and pauses the simulation.
+#[derive(Event, Clone, Copy)]
+pub struct ScrubHistory(pub isize);
+
+/// Sent when a state from [`SimulationHistory`] is restored.
+#[derive(Event, Clone, Copy)]
+pub struct HistoryRestored;
+
+/// Everything that is recorded in [`SimulationHistory`].
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct HistoryEntities<'w, 's> {
+    clock: ResMut<'w, SimulationClock>,
+    bodies: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static mut Transform,
+            Option<&'static mut PhysicsTransform>,
+            Option<&'static mut RigidBody>,
+        ),
+        With<Body>,
+    >,
+    springs: Query<'w, 's, (Entity, &'static mut Spring)>,
+    distance_constraints: Query<'w, 's, (Entity, &'static mut DistanceConstraint)>,
+    pulley_constraints: Query<'w, 's, (Entity, &'static mut PulleyConstraint)>,
+}
+
+impl HistoryEntities<'_, '_> {
+    fn capture(&self) -> Snapshot {
+        Snapshot {
+            clock: *self.clock,
+            bodies: self
+                .bodies
+                .iter()
+                .map(|(entity, transform, physics_transform, rigid_body)| {
+                    let physics_transform = PhysicsTransform::current(physics_transform, transform);
+                    let body = rigid_body.map(BodySnapshot::capture);
+                    (entity, physics_transform, body)
+                })
+                .collect(),
+            springs: self.springs.iter().map(|(e, s)| (e, *s)).collect(),
+            distance_constraints: self
+                .distance_constraints
+                .iter()
+                .map(|(e, c)| (e, *c))
+                .collect(),
+            pulley_constraints: self
+                .pulley_constraints
+                .iter()
+                .map(|(e, c)| (e, *c))
+                .collect(),
+        }
+    }
+
+    /// Restores the `snapshot`. Despawned entities are skipped.
+    fn restore(&mut self, snapshot: &Snapshot) {
+        *self.clock = snapshot.clock;
+        for (entity, saved_transform, saved_body) in snapshot.bodies.iter() {
+            let Ok((_, mut transform, physics_transform, rigid_body)) =
+                self.bodies.get_mut(*entity)
+            else {
+                continue;
+            };
+            saved_transform.write_to(&mut transform);
+            if let Some(mut physics_transform) = physics_transform {
+                *physics_transform = *saved_transform;
+            }
+            if let (Some(mut rigid_body), Some(saved_body)) = (rigid_body, saved_body) {
+                saved_body.restore(&mut rigid_body);
+            }
+        }
+        for (entity, saved) in snapshot.springs.iter() {
+            if let Ok((_, mut spring)) = self.springs.get_mut(*entity) {
+                *spring = *saved;
+            }
+        }
+        for (entity, saved) in snapshot.distance_constraints.iter() {
+            if let Ok((_, mut constraint)) = self.distance_constraints.get_mut(*entity) {
+                *constraint = *saved;
+            }
+        }
+        for (entity, saved) in snapshot.pulley_constraints.iter() {
+            if let Ok((_, mut constraint)) = self.pulley_constraints.get_mut(*entity) {
+                *constraint = *saved;
+            }
+        }
+    }
+
+    /// Restores the state `offset` steps away from the current one.
+    /// Returns `false` if there is nothing to restore.
+    fn seek(
+        &mut self,
+        history: &mut SimulationHistory,
+        offset: isize,
+        restored: &mut EventWriter<HistoryRestored>,
+    ) -> bool {
+        if history.position().is_none() && history.capacity > 0 {
+            // Keep the latest state to be able to come back to it
+            history.record(self.capture());
+        }
+        let Some(snapshot) = history.seek(offset) else {
+            return false;
+        };
+        self.restore(snapshot);
+        restored.send(HistoryRestored);
+        true
+    }
+}
+
+/// Records the state of the simulation before a step.
+pub fn record_history(mut history: ResMut<SimulationHistory>, entities: HistoryEntities) {
+    if history.capacity == 0 {
+        history.clear();
+        return;
+    }
+    history.record(entities.capture());
+}
+
+/// Handles [`ScrubHistory`] events.
+pub fn handle_history_requests(
+    mut events: EventReader<ScrubHistory>,
+    mut history: ResMut<SimulationHistory>,
+    mut entities: HistoryEntities,
+    mut state: ResMut<SimulationState>,
+    mut restored: EventWriter<HistoryRestored>,
+) {
+    for ScrubHistory(offset) in events.read() {
+        state.pause();
+        entities.seek(&mut history, *offset, &mut restored);
+    }
+}
+
+/// Restores the previous recorded step while the simulation is [`SimulationState::Rewinding`].
+pub fn rewind_simulation(
+    mut history: ResMut<SimulationHistory>,
+    mut entities: HistoryEntities,
+    mut state: ResMut<SimulationState>,
+    mut restored: EventWriter<HistoryRestored>,
+) {
+    if !entities.seek(&mut history, -1, &mut restored) || history.position() == Some(0) {
+        state.pause();
+    }
+}
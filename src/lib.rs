@@ -1,19 +1,39 @@
 use bevy::prelude::*;
+use body::physics_transform::insert_physics_transforms;
+use clock::{advance_simulation_clock, SimulationClock};
 use constraint::{
-    distance::system::{solve_distance_constraints, update_distance_constraints_transformation},
-    pulley::system::{solve_pulley_constraints, update_pulley_constraints_transformation},
+    distance::system::update_distance_constraints_transformation,
+    pulley::system::update_pulley_constraints_transformation,
+};
+use control::{
+    count_simulation_step, handle_step_requests, simulation_is_running, SimulationSet,
+    SimulationState, StepSimulation,
+};
+use energy::{
+    reset_energy_projection, update_energy_for_rigid_bodies, update_energy_for_springs,
+    EnergyProjection,
+};
+use history::{
+    handle_history_requests, record_history, rewind_simulation, HistoryRestored, ScrubHistory,
+    SimulationHistory,
 };
-use energy::{update_energy_for_rigid_bodies, update_energy_for_springs};
 use settings::SettingsResource;
-use solver::{clean_forces_and_torque, gravity, step_in_simulation};
-use springs::systems::{handle_spring_forces, update_spring_transformation};
+use solver::{clean_forces_and_torque, step_in_simulation, IntegrationStats};
+use springs::systems::update_spring_transformation;
+use wind::Wind;
 
 pub mod body;
+pub mod clock;
 pub mod constraint;
+pub mod control;
 pub mod energy;
+pub mod history;
+pub mod math;
 pub mod settings;
 pub mod solver;
 pub mod springs;
+pub mod wind;
+pub mod world;
 
 pub struct PDRustPlugin;
 
@@ -22,19 +42,55 @@ impl bevy::prelude::Plugin for PDRustPlugin {
         app.add_systems(
             FixedUpdate,
             (
-                clean_forces_and_torque,
-                gravity.after(clean_forces_and_torque),
-                handle_spring_forces.after(gravity),
-                solve_distance_constraints.after(handle_spring_forces),
-                solve_pulley_constraints.after(solve_distance_constraints),
-                step_in_simulation.after(solve_pulley_constraints),
+                record_history.before(advance_simulation_clock),
+                advance_simulation_clock,
+                clean_forces_and_torque.after(advance_simulation_clock),
+                step_in_simulation.after(clean_forces_and_torque),
                 update_energy_for_springs.after(update_spring_transformation),
                 update_energy_for_rigid_bodies.after(update_energy_for_springs),
                 update_spring_transformation.after(step_in_simulation),
                 update_distance_constraints_transformation.after(step_in_simulation),
                 update_pulley_constraints_transformation.after(step_in_simulation),
+                count_simulation_step
+                    .after(update_energy_for_rigid_bodies)
+                    .after(update_distance_constraints_transformation)
+                    .after(update_pulley_constraints_transformation),
+            )
+                .in_set(SimulationSet),
+        )
+        .configure_sets(FixedUpdate, SimulationSet.run_if(simulation_is_running))
+        .add_systems(
+            FixedUpdate,
+            rewind_simulation.run_if(resource_equals(SimulationState::Rewinding)),
+        )
+        .add_systems(PreUpdate, insert_physics_transforms)
+        .add_systems(
+            Update,
+            (
+                handle_step_requests,
+                handle_history_requests,
+                reset_energy_projection.after(handle_history_requests),
+                // Restored states are shown while the simulation is not running
+                (
+                    update_spring_transformation,
+                    update_distance_constraints_transformation,
+                    update_pulley_constraints_transformation,
+                    update_energy_for_springs.after(update_spring_transformation),
+                    update_energy_for_rigid_bodies.after(update_energy_for_springs),
+                )
+                    .after(handle_history_requests)
+                    .run_if(on_event::<HistoryRestored>()),
             ),
         )
-        .insert_resource(SettingsResource::default());
+        .add_event::<StepSimulation>()
+        .add_event::<ScrubHistory>()
+        .add_event::<HistoryRestored>()
+        .insert_resource(SimulationState::default())
+        .insert_resource(SimulationHistory::default())
+        .insert_resource(SettingsResource::default())
+        .insert_resource(IntegrationStats::default())
+        .insert_resource(EnergyProjection::default())
+        .insert_resource(Wind::default())
+        .insert_resource(SimulationClock::default());
     }
 }
@@ -0,0 +1,88 @@
+//! Numeric types of the simulation core.
+//!
+//! By default the simulation runs in single precision. With the `f64` feature all physics state
+//! (positions, momenta, inertia tensors, forces) is stored and integrated in double precision,
+//! which keeps long runs (e.g. orbits or slow pendulums) from drifting. Bevy's `Transform` stays
+//! single precision and is only used for rendering.
+
+use bevy::prelude::{Quat, Vec3};
+
+#[cfg(feature = "f64")]
+pub use bevy::math::{DMat3 as Matrix, DQuat as Quaternion, DVec3 as Vector};
+#[cfg(not(feature = "f64"))]
+pub use bevy::math::{Mat3 as Matrix, Quat as Quaternion, Vec3 as Vector};
+
+/// Constants (e.g. `PI`) of the [`Real`] type
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts;
+/// Constants (e.g. `PI`) of the [`Real`] type
+#[cfg(feature = "f64")]
+pub use std::f64::consts;
+
+/// Scalar type of the simulation core
+#[cfg(not(feature = "f64"))]
+pub type Real = f32;
+/// Scalar type of the simulation core
+#[cfg(feature = "f64")]
+pub type Real = f64;
+
+/// Converts a scalar of the simulation core to a render one.
+/// Use `Real::from` for the opposite conversion.
+#[allow(clippy::unnecessary_cast)]
+pub fn to_f32(x: Real) -> f32 {
+    x as f32
+}
+
+/// Converts a scalar of the simulation core to `f64`.
+#[allow(clippy::unnecessary_cast)]
+pub fn to_f64(x: Real) -> f64 {
+    x as f64
+}
+
+/// Converts a vector of the simulation core to a render one.
+#[cfg(not(feature = "f64"))]
+pub fn to_vec3(v: Vector) -> Vec3 {
+    v
+}
+
+/// Converts a vector of the simulation core to a render one.
+#[cfg(feature = "f64")]
+pub fn to_vec3(v: Vector) -> Vec3 {
+    v.as_vec3()
+}
+
+/// Converts a render vector to a vector of the simulation core.
+#[cfg(not(feature = "f64"))]
+pub fn from_vec3(v: Vec3) -> Vector {
+    v
+}
+
+/// Converts a render vector to a vector of the simulation core.
+#[cfg(feature = "f64")]
+pub fn from_vec3(v: Vec3) -> Vector {
+    v.as_dvec3()
+}
+
+/// Converts a rotation of the simulation core to a render one.
+#[cfg(not(feature = "f64"))]
+pub fn to_quat(q: Quaternion) -> Quat {
+    q
+}
+
+/// Converts a rotation of the simulation core to a render one.
+#[cfg(feature = "f64")]
+pub fn to_quat(q: Quaternion) -> Quat {
+    q.as_f32()
+}
+
+/// Converts a render rotation to a rotation of the simulation core.
+#[cfg(not(feature = "f64"))]
+pub fn from_quat(q: Quat) -> Quaternion {
+    q
+}
+
+/// Converts a render rotation to a rotation of the simulation core.
+#[cfg(feature = "f64")]
+pub fn from_quat(q: Quat) -> Quaternion {
+    q.as_f64()
+}
@@ -1,14 +1,70 @@
 use bevy::prelude::*;
 
+use crate::{
+    math::{Real, Vector},
+    solver::SimulationSolver,
+};
+
 #[derive(Resource)]
 pub struct SettingsResource {
     pub method: IntergrationMethod,
+    /// Number of substeps per `FixedUpdate`. Forces and constraints are re-evaluated every
+    /// substep, so more substeps improve accuracy of both.
     pub integration_substeps: usize,
+    /// Maximum number of conjugate gradient iterations of [`IntergrationMethod::ImplicitEuler`]
+    pub implicit_solver_iterations: usize,
+    /// Local error tolerance of [`IntergrationMethod::DormandPrince`]
+    pub adaptive_tolerance: Real,
+    /// Solve rotation in **Body** coordinates with an implicit gyroscopic term.
+    /// Keeps fast-spinning asymmetric bodies stable. Used by Euler, Verlet and implicit Euler
+    /// methods.
+    pub implicit_gyroscopic_torque: bool,
+    /// Number of constraint solver iterations per integration substep
     pub constraints_substeps: usize,
-    pub baumgarte_constant: f32,
-    pub gravity_vector: Vec3,
+    pub baumgarte_constant: Real,
+    /// Put islands of bodies (bodies connected with springs and constraints) to sleep when
+    /// they are at rest. Sleeping bodies are not integrated, and their springs and constraints
+    /// are not solved. They wake up when a force, a torque or a pulse is applied to any of them,
+    /// when the wind blows at one with drag, or when they get connected to an awake body or to a
+    /// moving kinematic one. Bodies with drag do not fall asleep in the wind.
+    /// Kinetic energy left in bodies that fall asleep is counted as dissipated, see
+    /// [`crate::body::RigidBody::dissipated_energy`].
+    pub allow_sleeping: bool,
+    /// Speed below which a body is considered resting
+    pub sleep_linear_velocity: Real,
+    /// Angular speed below which a body is considered resting
+    pub sleep_angular_velocity: Real,
+    /// For how long (in simulated seconds) all bodies of an island must rest before it falls
+    /// asleep
+    pub time_to_sleep: Real,
+    pub gravity_vector: Vector,
+    /// Integrate bodies and accumulate forces on multiple threads of `ComputeTaskPool`.
+    /// Results are the same as with a single thread, including deterministic mode.
+    pub parallel: bool,
     pub slow_motion_koef: f32,
     pub print_energy_in_console: bool,
+    /// Rescale velocities after every `FixedUpdate` so that the total energy stays at its value
+    /// when the projection was turned on. It hides energy drift of integration methods in
+    /// conservative scenes (springs without damping), but it is not physics: the correction is
+    /// reported by [`crate::energy::EnergyProjection::correction`].
+    pub energy_projection: bool,
+    /// Deterministic mode: two runs of the same scene produce bit-identical trajectories.
+    ///
+    /// Every `FixedUpdate` advances the simulation by exactly the fixed timestep divided by
+    /// `slow_motion_koef`, and bodies, springs and constraints are processed in the order of their
+    /// entities instead of the query order.
+    ///
+    /// Reproducible are [`crate::clock::SimulationClock`], `Transform`,
+    /// [`crate::body::PhysicsTransform`] and [`crate::body::RigidBody`] of bodies, and
+    /// [`crate::energy::Energy`], as long as:
+    /// - entities are spawned in the same order and settings are not changed during the run;
+    /// - forces are applied by user systems in a reproducible way;
+    /// - the same binary runs on the same platform (floating point functions like `sin` and
+    ///   `sqrt` may differ between platforms and compiler versions).
+    ///
+    /// The number of `FixedUpdate`s per frame still depends on wall time, so only the state after
+    /// a given number of steps ([`crate::clock::SimulationClock::steps`]) is reproducible.
+    pub deterministic: bool,
 }
 
 impl Default for SettingsResource {
@@ -16,15 +72,45 @@ impl Default for SettingsResource {
         Self {
             method: IntergrationMethod::EulerMethod,
             integration_substeps: 8,
+            implicit_solver_iterations: 64,
+            adaptive_tolerance: 1e-4,
+            implicit_gyroscopic_torque: false,
             constraints_substeps: 16,
             baumgarte_constant: 0.01,
+            allow_sleeping: false,
+            sleep_linear_velocity: 0.05,
+            sleep_angular_velocity: 0.05,
+            time_to_sleep: 1.0,
             slow_motion_koef: 1.0,
-            gravity_vector: Vec3::new(0.0, -9.81, 0.0),
+            gravity_vector: Vector::new(0.0, -9.81, 0.0),
+            parallel: true,
             print_energy_in_console: false,
+            energy_projection: false,
+            deterministic: false,
         }
     }
 }
 
 pub enum IntergrationMethod {
+    /// Explicit Euler method. Position is updated with the velocity from the beginning of the step.
     EulerMethod,
+    /// Symplectic (semi-implicit) Euler method. Velocity is updated first and then used to
+    /// update position and orientation. Keeps energy of conservative systems bounded.
+    SemiImplicitEuler,
+    /// Classical fourth-order Runge-Kutta method. Gravity and spring forces are re-evaluated at
+    /// intermediate states, so it is noticeably more expensive than Euler methods.
+    RungeKutta4,
+    /// Velocity Verlet (leapfrog) method. Second-order and time-reversible, which keeps stiff
+    /// springs stable with a moderate number of substeps.
+    VelocityVerlet,
+    /// Linearized implicit (backward) Euler method. Spring force Jacobians are assembled every
+    /// step and the resulting linear system is solved with conjugate gradient method.
+    /// Stable for very stiff springs and dampers even with large time steps.
+    ImplicitEuler,
+    /// Adaptive Dormand-Prince 4(5) method. Step size is chosen to keep local error below
+    /// `adaptive_tolerance`, `integration_substeps` is only used as an initial guess.
+    /// Number of steps made is available in [`crate::solver::IntegrationStats`].
+    DormandPrince,
+    /// User-defined integration method.
+    Custom(Box<dyn SimulationSolver>),
 }
@@ -0,0 +1,123 @@
+use std::ops::{Add, Mul};
+
+use crate::math::{Real, Vector};
+
+use super::{
+    exponential_map,
+    parallel::{for_each_mut, map},
+    rotate_around_center_of_mass,
+    state::{BodyState, PhysicsState},
+};
+
+/// Time derivative of a [`BodyState`].
+///
+/// Orientation is handled as in Runge-Kutta-Munthe-Kaas methods: intermediate orientations are
+/// `exp(u) * q0`, where `q0` is the orientation at the beginning of the step, and `spin` is the
+/// derivative of `u` (in **World** coordinates). This keeps every stage on the rotation group,
+/// so no renormalization of quaternions is needed.
+#[derive(Clone, Copy, Default)]
+pub struct Derivative {
+    pub velocity: Vector,
+    pub spin: Vector,
+    pub force: Vector,
+    pub torque: Vector,
+}
+
+impl Add for Derivative {
+    type Output = Derivative;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Derivative {
+            velocity: self.velocity + rhs.velocity,
+            spin: self.spin + rhs.spin,
+            force: self.force + rhs.force,
+            torque: self.torque + rhs.torque,
+        }
+    }
+}
+
+impl Mul<Real> for Derivative {
+    type Output = Derivative;
+
+    fn mul(self, rhs: Real) -> Self::Output {
+        Derivative {
+            velocity: self.velocity * rhs,
+            spin: self.spin * rhs,
+            force: self.force * rhs,
+            torque: self.torque * rhs,
+        }
+    }
+}
+
+/// Inverse of the derivative of the exponential map: `dexp_u^{-1}(omega)`.
+/// The series is truncated after the second commutator, which is enough for fourth- and
+/// fifth-order methods.
+fn dexp_inv(u: Vector, omega: Vector) -> Vector {
+    let commutator = u.cross(omega);
+    omega - 0.5 * commutator + u.cross(commutator) / 12.0
+}
+
+/// Evaluates derivatives of all bodies in the current `state`.
+/// `initial` is the state at the beginning of the step.
+pub fn evaluate(state: &mut PhysicsState, initial: &[BodyState]) -> Vec<Derivative> {
+    state.accumulate_forces();
+    map(&state.bodies, state.parallel, |i, body_state| {
+        let initial = &initial[i];
+        match body_state.rigid_body {
+            Some(body) => {
+                let mut relative_rotation =
+                    body_state.transform.rotation * initial.transform.rotation.inverse();
+                if relative_rotation.w < 0.0 {
+                    relative_rotation = -relative_rotation;
+                }
+                let u = relative_rotation.to_scaled_axis();
+                let omega = body.get_angular_velocity(&body_state.transform);
+                Derivative {
+                    velocity: body.get_velocity(),
+                    spin: dexp_inv(u, omega),
+                    force: body.force,
+                    torque: body.torque,
+                }
+            }
+            None => Derivative::default(),
+        }
+    })
+}
+
+/// Sets `state` to `initial + derivatives * dt`.
+pub fn advance(
+    state: &mut PhysicsState,
+    initial: &[BodyState],
+    derivatives: &[Derivative],
+    dt: Real,
+) {
+    for_each_mut(&mut state.bodies, state.parallel, |i, body_state| {
+        let (initial, derivative) = (&initial[i], &derivatives[i]);
+        let (Some(body), Some(initial_body)) =
+            (body_state.rigid_body.as_mut(), initial.rigid_body.as_ref())
+        else {
+            return;
+        };
+        let transform = &mut body_state.transform;
+        *transform = initial.transform;
+        transform.translation += derivative.velocity * dt;
+        let rotation =
+            (exponential_map(derivative.spin, dt) * initial.transform.rotation).normalize();
+        rotate_around_center_of_mass(initial_body, transform, rotation);
+        body.pulse = initial_body.pulse + derivative.force * dt;
+        body.angular_momentum = initial_body.angular_momentum + derivative.torque * dt;
+    });
+}
+
+/// Computes a linear combination `sum(coefficients[i] * derivatives[i])` for every body.
+pub fn combine(derivatives: &[Vec<Derivative>], coefficients: &[Real]) -> Vec<Derivative> {
+    let bodies = derivatives.first().map(|d| d.len()).unwrap_or(0);
+    (0..bodies)
+        .map(|body| {
+            derivatives
+                .iter()
+                .zip(coefficients)
+                .fold(Derivative::default(), |acc, (d, &c)| acc + d[body] * c)
+        })
+        .collect()
+}
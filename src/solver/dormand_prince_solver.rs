@@ -0,0 +1,144 @@
+use bevy::prelude::Resource;
+
+use crate::math::Real;
+
+use super::{
+    derivative::{advance, combine, evaluate, Derivative},
+    state::{BodyState, PhysicsState},
+    SimulationSolver,
+};
+
+/// Adaptive Dormand-Prince 4(5) method.
+///
+/// Every step is made with a fifth-order Runge-Kutta method, and the difference with the embedded
+/// fourth-order solution is used as an error estimate. Steps with error above `tolerance` are
+/// rejected and retried with a smaller step, so the solver refines exactly where the motion is
+/// fast (e.g. near the bottom of a pendulum swing).
+#[derive(Resource)]
+pub struct DormandPrinceSolver {
+    /// Maximum allowed local error relative to the magnitude of the state
+    pub tolerance: Real,
+}
+
+/// Coefficients of intermediate stages
+const A: [&[Real]; 6] = [
+    &[1.0 / 5.0],
+    &[3.0 / 40.0, 9.0 / 40.0],
+    &[44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0],
+    &[
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+    ],
+    &[
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+    ],
+    &[
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+
+/// Difference between weights of fifth and fourth order solutions
+const ERROR: [Real; 7] = [
+    71.0 / 57600.0,
+    0.0,
+    -71.0 / 16695.0,
+    71.0 / 1920.0,
+    -17253.0 / 339200.0,
+    22.0 / 525.0,
+    -1.0 / 40.0,
+];
+
+/// Minimum step is `dt / MAX_STEPS`. Steps of this size are accepted regardless of the error.
+const MAX_STEPS: usize = 1024;
+
+impl DormandPrinceSolver {
+    /// Makes a fifth-order step from `initial` and returns the error estimate relative to
+    /// `tolerance` (the step is acceptable if it is not greater than 1). The error is infinite if
+    /// the estimate is not finite.
+    fn try_step(&self, state: &mut PhysicsState, initial: &[BodyState], dt: Real) -> Real {
+        let mut k: Vec<Vec<Derivative>> = Vec::with_capacity(7);
+        k.push(evaluate(state, initial));
+        for coefficients in A.iter() {
+            advance(state, initial, &combine(&k, coefficients), dt);
+            k.push(evaluate(state, initial));
+        }
+        // The last stage is evaluated at the fifth-order solution, so `state` already holds it.
+
+        let error = combine(&k, &ERROR);
+        initial
+            .iter()
+            .zip(error.iter())
+            .filter_map(|(initial, error)| initial.rigid_body.map(|body| (body, initial, error)))
+            .map(|(body, initial, error)| {
+                let scale = |value: Real| self.tolerance * (1.0 + value);
+                let translation = initial.transform.translation.length();
+                [
+                    dt * error.velocity.length() / scale(translation),
+                    dt * error.spin.length() / scale(0.0),
+                    dt * error.force.length() / scale(body.pulse.length()),
+                    dt * error.torque.length() / scale(body.angular_momentum.length()),
+                ]
+                .into_iter()
+                // `max` ignores NaN, so a blown up estimate must not count as a small error
+                .map(|error| {
+                    if error.is_finite() {
+                        error
+                    } else {
+                        Real::INFINITY
+                    }
+                })
+                .fold(0.0, Real::max)
+            })
+            .fold(0.0, Real::max)
+    }
+}
+
+impl SimulationSolver for DormandPrinceSolver {
+    fn step(&self, state: &mut PhysicsState, dt: Real) {
+        let initial = state.bodies.clone();
+        self.try_step(state, &initial, dt);
+    }
+
+    fn integrate(&self, state: &mut PhysicsState, dt: Real, substeps: usize) -> usize {
+        let min_step = dt / MAX_STEPS as Real;
+        let mut step = dt / substeps as Real;
+        let mut remaining = dt;
+        let mut steps = 0;
+
+        while remaining > 0.0 {
+            step = step.max(min_step).min(remaining);
+            let initial = state.bodies.clone();
+            state.prepare_step(step);
+            let prepared = state.bodies.clone();
+            let error = self.try_step(state, &prepared, step);
+
+            if error <= 1.0 || step <= min_step {
+                remaining -= step;
+                steps += 1;
+            } else {
+                state.bodies = initial;
+            }
+
+            let factor = if error == 0.0 {
+                5.0
+            } else if error.is_finite() {
+                0.9 * error.powf(-0.2)
+            } else {
+                0.2
+            };
+            step *= factor.clamp(0.2, 5.0);
+        }
+        steps
+    }
+}
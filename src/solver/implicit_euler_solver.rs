@@ -0,0 +1,157 @@
+use bevy::prelude::Resource;
+
+use crate::{
+    body::Body,
+    math::{Matrix, Real, Vector},
+};
+
+use super::{rotate_body, state::PhysicsState, SimulationSolver};
+
+/// Linearized implicit (backward) Euler method.
+///
+/// Velocities of bodies after a step are found from
+/// `(M - dt * df/dv - dt^2 * df/dx) dv = dt * (f + dt * df/dx * v)`,
+/// where `df/dx` and `df/dv` are spring force Jacobians (see [`crate::springs::Spring::compute_jacobians`]).
+/// The system is solved with conjugate gradient method without assembling the matrix.
+///
+/// Only translational motion is treated implicitly: spring Jacobians are taken with respect to
+/// body positions, angular momentum is updated explicitly with torques at the beginning of the step.
+/// This is enough to keep very stiff spring networks stable with large time steps.
+#[derive(Resource)]
+pub struct ImplicitEulerSolver {
+    /// Maximum number of conjugate gradient iterations per step
+    pub max_iterations: usize,
+}
+
+/// Relative residual at which conjugate gradient method stops
+const TOLERANCE: Real = 1e-5;
+
+/// Jacobians of a spring connecting bodies `first` and `second`.
+struct SpringJacobian {
+    first: usize,
+    second: usize,
+    stiffness: Matrix,
+    damping: Matrix,
+}
+
+fn dot(a: &[Vector], b: &[Vector]) -> Real {
+    a.iter().zip(b).map(|(x, y)| x.dot(*y)).sum()
+}
+
+/// Solves `A x = rhs` for symmetric positive-definite `A` given by its product with a vector.
+fn conjugate_gradient(
+    multiply: impl Fn(&[Vector]) -> Vec<Vector>,
+    rhs: &[Vector],
+    max_iterations: usize,
+) -> Vec<Vector> {
+    let mut x = vec![Vector::ZERO; rhs.len()];
+    let mut r = rhs.to_vec();
+    let mut p = r.clone();
+    let mut rs_old = dot(&r, &r);
+    let threshold = rs_old * TOLERANCE * TOLERANCE;
+
+    for _ in 0..max_iterations {
+        if rs_old <= threshold || rs_old == 0.0 {
+            break;
+        }
+        let ap = multiply(&p);
+        let alpha = rs_old / dot(&p, &ap);
+        for i in 0..x.len() {
+            x[i] += alpha * p[i];
+            r[i] -= alpha * ap[i];
+        }
+        let rs_new = dot(&r, &r);
+        let beta = rs_new / rs_old;
+        for i in 0..p.len() {
+            p[i] = r[i] + beta * p[i];
+        }
+        rs_old = rs_new;
+    }
+    x
+}
+
+impl SimulationSolver for ImplicitEulerSolver {
+    fn step(&self, state: &mut PhysicsState, dt: Real) {
+        let implicit_gyroscopic = state.implicit_gyroscopic;
+
+        // Static bodies have zero inversed mass, their velocity change is always zero
+        let masses: Vec<Option<Real>> = state
+            .bodies
+            .iter()
+            .map(|s| s.rigid_body.map(|b| b.mass))
+            .collect();
+        // Springs attached to kinematic bodies feel their velocity
+        let velocities: Vec<Vector> = state.bodies.iter().map(|s| s.get_velocity()).collect();
+        let jacobians: Vec<SpringJacobian> = state
+            .springs
+            .iter()
+            .map(|spring_state| {
+                let spring = &spring_state.spring;
+                let t1 = &state.bodies[spring_state.first].transform;
+                let t2 = &state.bodies[spring_state.second].transform;
+                let sp1 =
+                    Body.body_to_world_coordinates(spring.first_body_attachment_point_offset, t1);
+                let sp2 =
+                    Body.body_to_world_coordinates(spring.second_body_attachment_point_offset, t2);
+                let (stiffness, damping) = spring.compute_jacobians(sp1, sp2);
+                SpringJacobian {
+                    first: spring_state.first,
+                    second: spring_state.second,
+                    stiffness,
+                    damping,
+                }
+            })
+            .collect();
+
+        let mut rhs: Vec<Vector> = state
+            .bodies
+            .iter()
+            .map(|s| s.rigid_body.map(|b| b.force * dt).unwrap_or(Vector::ZERO))
+            .collect();
+        for jacobian in jacobians.iter() {
+            let kv =
+                jacobian.stiffness * (velocities[jacobian.first] - velocities[jacobian.second]);
+            rhs[jacobian.first] += dt * dt * kv;
+            rhs[jacobian.second] -= dt * dt * kv;
+        }
+        for (value, mass) in rhs.iter_mut().zip(masses.iter()) {
+            if mass.is_none() {
+                *value = Vector::ZERO;
+            }
+        }
+
+        let multiply = |x: &[Vector]| {
+            let mut y: Vec<Vector> = x
+                .iter()
+                .zip(masses.iter())
+                .map(|(v, mass)| *v * mass.unwrap_or(0.0))
+                .collect();
+            for jacobian in jacobians.iter() {
+                let j = jacobian.damping * dt + jacobian.stiffness * (dt * dt);
+                let d = j * (x[jacobian.first] - x[jacobian.second]);
+                if masses[jacobian.first].is_some() {
+                    y[jacobian.first] -= d;
+                }
+                if masses[jacobian.second].is_some() {
+                    y[jacobian.second] += d;
+                }
+            }
+            y
+        };
+        let velocity_change = conjugate_gradient(multiply, &rhs, self.max_iterations);
+
+        for (i, body_state) in state.bodies.iter_mut().enumerate() {
+            let Some(body) = body_state.rigid_body.as_mut() else {
+                continue;
+            };
+            let transform = &mut body_state.transform;
+
+            let velocity = velocities[i] + velocity_change[i];
+            body.pulse = velocity * body.mass;
+            body.angular_momentum += body.torque * dt;
+
+            transform.translation += velocity * dt;
+            rotate_body(body, transform, dt, implicit_gyroscopic);
+        }
+    }
+}
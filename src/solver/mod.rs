@@ -1,45 +1,362 @@
-use crate::settings::{IntergrationMethod, SettingsResource};
+use crate::{
+    body::{Body, BodyType, PhysicsTransform},
+    clock::SimulationClock,
+    constraint::{distance::DistanceConstraint, pulley::PulleyConstraint},
+    energy::EnergyProjection,
+    math::{from_vec3, Matrix, Quaternion, Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    wind::Wind,
+};
 
-use self::euler_solver::EulerSolver;
-use bevy::prelude::*;
+use self::state::{
+    BodyState, DistanceConstraintState, PhysicsState, PulleyConstraintState, SpringState,
+};
+pub use self::{
+    dormand_prince_solver::DormandPrinceSolver, euler_solver::EulerSolver,
+    implicit_euler_solver::ImplicitEulerSolver, runge_kutta_solver::RungeKuttaSolver,
+    semi_implicit_euler_solver::SemiImplicitEulerSolver, verlet_solver::VerletSolver,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
 
 use super::body::RigidBody;
 
+mod derivative;
+mod dormand_prince_solver;
 mod euler_solver;
+mod implicit_euler_solver;
+mod parallel;
+mod runge_kutta_solver;
+mod semi_implicit_euler_solver;
+pub mod state;
+mod verlet_solver;
 
-trait SimulationSolver: Resource {
-    fn step(&self, body: Mut<RigidBody>, transform: Mut<Transform>, dt: f32);
+/// A numerical integration method.
+///
+/// Implement it to plug your own integrator into the simulation with
+/// [`IntergrationMethod::Custom`]. A solver is called `integration_substeps` times per
+/// `FixedUpdate` and must advance every rigid body of the `state` by `dt`. Bodies without
+/// rigid body (`rigid_body` is `None`) are static and must not be moved.
+///
+/// Before every step `force` and `torque` of every rigid body are computed and constraint
+/// impulses are applied (see [`PhysicsState::prepare_step`]).
+/// Call [`PhysicsState::accumulate_forces`] to re-evaluate forces after changing the `state`.
+///
+/// Velocity of a rigid body is the velocity of its center of mass, which may be offset from the
+/// origin of its transform. Use [`rotate_around_center_of_mass`] to change orientation.
+pub trait SimulationSolver: Send + Sync {
+    /// Advances every body of the `state` by `dt`.
+    fn step(&self, state: &mut PhysicsState, dt: Real);
+
+    /// Advances every body of the `state` by `dt` and returns the number of steps made.
+    ///
+    /// By default `dt` is split into `substeps` equal steps, and every step is preceded by
+    /// [`PhysicsState::prepare_step`]. Adaptive methods override it to choose step sizes on their
+    /// own.
+    fn integrate(&self, state: &mut PhysicsState, dt: Real, substeps: usize) -> usize {
+        let step_dt = dt / substeps as Real;
+        for _step in 0..substeps {
+            state.prepare_step(step_dt);
+            self.step(state, step_dt);
+        }
+        substeps
+    }
 }
 
-pub fn clean_forces_and_torque(mut query: Query<&mut RigidBody>) {
-    for mut body in query.iter_mut() {
-        body.force = Vec3::ZERO;
-        body.torque = Vec3::ZERO;
+/// Statistics of the last integration.
+#[derive(Resource, Default)]
+pub struct IntegrationStats {
+    /// Number of steps the solver made during the last `FixedUpdate`
+    pub steps: usize,
+    /// Number of sleeping bodies after the last `FixedUpdate`
+    pub sleeping_bodies: usize,
+}
+
+/// Returns rotation by angular velocity `omega` (in **World** coordinates) during `dt`.
+/// This is the exponential map of `omega * dt`, so fast spins are not distorted.
+pub fn exponential_map(omega: Vector, dt: Real) -> Quaternion {
+    Quaternion::from_scaled_axis(omega * dt)
+}
+
+/// Sets `rotation` of a `transform` of a `body`, turning the body around its center of mass
+/// rather than around the origin of the `transform`.
+pub fn rotate_around_center_of_mass(
+    body: &RigidBody,
+    transform: &mut PhysicsTransform,
+    rotation: Quaternion,
+) {
+    let center_of_mass = body.get_center_of_mass(transform);
+    transform.rotation = rotation;
+    transform.translation = center_of_mass - rotation * body.center_of_mass;
+}
+
+/// Rotates `transform` of a `body` during `dt`.
+///
+/// Lie group midpoint method is used: angular velocity is evaluated at the orientation in the
+/// middle of the step, and the orientation is updated with the [`exponential_map`]. Unlike adding
+/// `0.5 * omega * q * dt` to the quaternion, it keeps torque-free bodies spinning at a constant
+/// rate.
+pub fn integrate_rotation(body: &RigidBody, transform: &mut PhysicsTransform, dt: Real) {
+    let omega = body.get_angular_velocity(transform);
+    let half_step =
+        PhysicsTransform::from_rotation(exponential_map(omega, dt / 2.0) * transform.rotation);
+    let omega = body.get_angular_velocity(&half_step);
+    let rotation = (exponential_map(omega, dt) * transform.rotation).normalize();
+    rotate_around_center_of_mass(body, transform, rotation);
+}
+
+/// Maximum number of Newton iterations of [`integrate_rotation_implicit_gyroscopic`]
+const GYROSCOPIC_ITERATIONS: usize = 8;
+
+/// Returns a matrix `M` such that `M * x = v.cross(x)`.
+fn skew(v: Vector) -> Matrix {
+    Matrix::from_cols(
+        Vector::new(0.0, v.z, -v.y),
+        Vector::new(-v.z, 0.0, v.x),
+        Vector::new(v.y, -v.x, 0.0),
+    )
+}
+
+/// Rotates `transform` of a `body` during `dt` solving Euler's rigid body equations in **Body**
+/// coordinates with an implicit gyroscopic term.
+///
+/// Angular velocity in the middle of the step is found from `2 I (w - w0) + dt * w x I w = 0`
+/// with Newton iterations (the implicit midpoint rule, see also Erin Catto, "Numerical Methods",
+/// GDC 2015). Unlike explicit integration, it keeps kinetic energy as well as angular momentum,
+/// so asymmetric bodies spinning fast (e.g. around the intermediate axis) stay stable.
+/// Torques are not applied, `angular_momentum` of the `body` is left as is.
+pub fn integrate_rotation_implicit_gyroscopic(
+    body: &mut RigidBody,
+    transform: &mut PhysicsTransform,
+    dt: Real,
+) {
+    let rotation = Matrix::from_quat(transform.rotation);
+    let inertia = body.intertia_tensor_body;
+    let initial_omega = rotation.transpose() * body.get_angular_velocity(transform);
+
+    let mut omega = initial_omega;
+    for _ in 0..GYROSCOPIC_ITERATIONS {
+        let inertia_omega = inertia * omega;
+        let residual = 2.0 * inertia * (omega - initial_omega) + dt * omega.cross(inertia_omega);
+        let jacobian = 2.0 * inertia + dt * (skew(omega) * inertia - skew(inertia_omega));
+        let correction = jacobian.inverse() * residual;
+        omega -= correction;
+        if correction.length_squared() <= Real::EPSILON * omega.length_squared() {
+            break;
+        }
     }
+
+    let rotation = exponential_map(rotation * omega, dt) * transform.rotation;
+    // Angular velocity at the end of the step gives angular momentum of the same magnitude.
+    // The rotation is corrected by a tiny angle so that its direction stays the same too.
+    let omega = 2.0 * omega - initial_omega;
+    let angular_momentum = rotation * (inertia * omega);
+    let correction = Quaternion::from_rotation_arc(
+        angular_momentum.normalize(),
+        body.angular_momentum.normalize(),
+    );
+    rotate_around_center_of_mass(body, transform, (correction * rotation).normalize());
 }
 
-pub fn step_in_simulation(
-    mut query: Query<(&mut RigidBody, &mut Transform)>,
-    time: Res<Time>,
-    settings: Res<SettingsResource>,
+/// Rotates `transform` of a `body` during `dt` with the method chosen in `state`.
+fn rotate_body(
+    body: &mut RigidBody,
+    transform: &mut PhysicsTransform,
+    dt: Real,
+    implicit_gyroscopic: bool,
 ) {
-    let solver: &dyn SimulationSolver = match settings.method {
+    if body.angular_momentum == Vector::ZERO {
+        return;
+    }
+    // Singular tensors (e.g. of thin rods) can not be used in the implicit step
+    if implicit_gyroscopic && body.intertia_tensor_body.determinant() != 0.0 {
+        integrate_rotation_implicit_gyroscopic(body, transform, dt);
+    } else {
+        integrate_rotation(body, transform, dt);
+    }
+}
+
+pub fn clean_forces_and_torque(mut query: Query<&mut RigidBody>) {
+    query.par_iter_mut().for_each(|mut body| {
+        body.force = Vector::ZERO;
+        body.torque = Vector::ZERO;
+    });
+}
+
+/// Advances the `state` by `dt` with the integration method, substeps and constraint parameters
+/// from the `settings`. Returns the number of steps made by the solver.
+///
+/// Sleeping bodies are woken up if disturbed and skipped otherwise, and bodies at rest are put
+/// to sleep after the step. Gravity of the `state` is left as is.
+pub fn integrate_state(state: &mut PhysicsState, settings: &SettingsResource, dt: Real) -> usize {
+    let implicit_euler_solver = ImplicitEulerSolver {
+        max_iterations: settings.implicit_solver_iterations,
+    };
+    let dormand_prince_solver = DormandPrinceSolver {
+        tolerance: settings.adaptive_tolerance,
+    };
+    let solver: &dyn SimulationSolver = match &settings.method {
         IntergrationMethod::EulerMethod => &EulerSolver {},
+        IntergrationMethod::SemiImplicitEuler => &SemiImplicitEulerSolver {},
+        IntergrationMethod::RungeKutta4 => &RungeKuttaSolver {},
+        IntergrationMethod::VelocityVerlet => &VerletSolver {},
+        IntergrationMethod::ImplicitEuler => &implicit_euler_solver,
+        IntergrationMethod::DormandPrince => &dormand_prince_solver,
+        IntergrationMethod::Custom(solver) => solver.as_ref(),
     };
-    let slow_motion_koef: f32 = settings.slow_motion_koef;
-    let substeps = settings.integration_substeps;
-    let dt = time.delta_seconds() / slow_motion_koef as f32 / (substeps as f32);
-    for _step in 0..substeps {
-        for (body, transform) in query.iter_mut() {
-            solver.step(body, transform, dt);
-        }
-    }
+    state.constraint_iterations = settings.constraints_substeps;
+    state.baumgarte_constant = settings.baumgarte_constant;
+    state.implicit_gyroscopic = settings.implicit_gyroscopic_torque;
+    state.allow_sleeping = settings.allow_sleeping;
+    state.sleep_linear_velocity = settings.sleep_linear_velocity;
+    state.sleep_angular_velocity = settings.sleep_angular_velocity;
+    state.time_to_sleep = settings.time_to_sleep;
+    state.parallel = settings.parallel;
+
+    state.wake_up_bodies();
+    let sleeping = state.take_sleeping_bodies();
+    let steps = solver.integrate(state, dt, settings.integration_substeps);
+    state.restore_sleeping_bodies(sleeping);
+    state.update_sleeping(dt);
+
+    steps
+}
+
+/// Resources read and updated by [`step_in_simulation`].
+#[derive(SystemParam)]
+pub struct StepResources<'w> {
+    clock: Res<'w, SimulationClock>,
+    settings: Res<'w, SettingsResource>,
+    stats: ResMut<'w, IntegrationStats>,
+    energy_projection: ResMut<'w, EnergyProjection>,
+    wind: Res<'w, Wind>,
 }
 
-pub fn gravity(mut query: Query<(&mut RigidBody, &Transform)>, settings: Res<SettingsResource>) {
-    let gravity_acc = settings.gravity_vector;
-    for (mut body, transform) in query.iter_mut() {
-        let gravity = gravity_acc * body.mass;
-        body.apply_force_body_coords(Vec3::ZERO, gravity, transform)
+/// Runs the whole simulation pipeline for every integration substep:
+/// force accumulation (external forces, gravity and springs), constraint solving and
+/// integration.
+///
+/// Forces applied to rigid bodies after [`clean_forces_and_torque`] are treated as external
+/// and act during the whole `FixedUpdate`.
+///
+/// Bodies are integrated from their [`PhysicsTransform`], and both it and `Transform` are updated
+/// after the step.
+#[allow(clippy::type_complexity)]
+pub fn step_in_simulation(
+    mut bodies_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            Option<&mut PhysicsTransform>,
+            Option<&mut RigidBody>,
+            Option<&BodyType>,
+        ),
+        With<Body>,
+    >,
+    springs_query: Query<(Entity, &Spring)>,
+    distance_constraints_query: Query<(Entity, &DistanceConstraint)>,
+    pulley_constraints_query: Query<(Entity, &PulleyConstraint, &Transform), Without<Body>>,
+    resources: StepResources,
+) {
+    let StepResources {
+        clock,
+        settings,
+        mut stats,
+        mut energy_projection,
+        wind,
+    } = resources;
+    let dt = clock.delta_seconds();
+
+    let mut bodies: Vec<_> = bodies_query.iter().collect();
+    let mut springs: Vec<_> = springs_query.iter().collect();
+    let mut distance_constraints: Vec<_> = distance_constraints_query.iter().collect();
+    let mut pulley_constraints: Vec<_> = pulley_constraints_query.iter().collect();
+    if settings.deterministic {
+        // Query order depends on archetypes and tables, entities give a stable order
+        bodies.sort_unstable_by_key(|(entity, _, _, _, _)| *entity);
+        springs.sort_unstable_by_key(|(entity, _)| *entity);
+        distance_constraints.sort_unstable_by_key(|(entity, _)| *entity);
+        pulley_constraints.sort_unstable_by_key(|(entity, _, _)| *entity);
     }
+
+    let mut indices = HashMap::new();
+    let mut body_states = Vec::new();
+    for (entity, transform, physics_transform, rigid_body, body_type) in bodies {
+        indices.insert(entity, body_states.len());
+        let physics_transform = PhysicsTransform::current(physics_transform, transform);
+        body_states.push(match body_type {
+            Some(BodyType::Static) => BodyState::new(physics_transform, None),
+            Some(&BodyType::Kinematic {
+                velocity,
+                angular_velocity,
+            }) => BodyState::kinematic(physics_transform, velocity, angular_velocity),
+            _ => BodyState::new(physics_transform, rigid_body.copied()),
+        });
+    }
+    let springs = springs
+        .into_iter()
+        .map(|(_, spring)| SpringState {
+            first: indices[&spring.first_body],
+            second: indices[&spring.second_body],
+            spring: *spring,
+        })
+        .collect();
+    let mut state = PhysicsState::new(body_states, springs, settings.gravity_vector);
+    state.wind = wind.clone();
+    state.distance_constraints = distance_constraints
+        .into_iter()
+        .map(|(_, constraint)| {
+            let (first, second) = constraint.bodies();
+            DistanceConstraintState {
+                first: indices[&first],
+                second: indices[&second],
+                constraint: *constraint,
+            }
+        })
+        .collect();
+    state.pulley_constraints = pulley_constraints
+        .into_iter()
+        .map(|(_, constraint, transform)| {
+            let (first, second) = constraint.bodies();
+            PulleyConstraintState {
+                first: indices[&first],
+                second: indices[&second],
+                constraint: *constraint,
+                pulley_position: from_vec3(transform.translation),
+            }
+        })
+        .collect();
+    if settings.energy_projection {
+        energy_projection.start(&state);
+    } else {
+        energy_projection.reset();
+    }
+    stats.steps = integrate_state(&mut state, &settings, dt);
+    if settings.energy_projection {
+        energy_projection.apply(&mut state);
+    }
+    stats.sleeping_bodies = state
+        .bodies
+        .iter()
+        .filter(|s| s.rigid_body.map(|b| b.sleeping).unwrap_or(false))
+        .count();
+
+    bodies_query.par_iter_mut().for_each(
+        |(entity, mut transform, physics_transform, rigid_body, _)| {
+            let body_state = &state.bodies[indices[&entity]];
+            let moved = match (rigid_body, body_state.rigid_body) {
+                (Some(mut rigid_body), Some(new_rigid_body)) => {
+                    *rigid_body = new_rigid_body;
+                    true
+                }
+                _ => matches!(body_state.body_type, BodyType::Kinematic { .. }),
+            };
+            if moved {
+                body_state.transform.write_to(&mut transform);
+                if let Some(mut physics_transform) = physics_transform {
+                    *physics_transform = body_state.transform;
+                }
+            }
+        },
+    );
 }
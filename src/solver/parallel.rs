@@ -0,0 +1,74 @@
+use bevy::tasks::{ComputeTaskPool, TaskPool};
+
+/// Number of items processed by a single task.
+/// Shorter slices are processed on the current thread, where spawning tasks costs more than
+/// it saves.
+const CHUNK_SIZE: usize = 256;
+
+fn task_pool() -> &'static TaskPool {
+    ComputeTaskPool::get_or_init(TaskPool::default)
+}
+
+/// Whether `len` items are worth splitting between threads.
+fn split(parallel: bool, len: usize) -> bool {
+    parallel && len > CHUNK_SIZE && task_pool().thread_num() > 1
+}
+
+/// Calls `f` with an index and a mutable reference for every item.
+/// Items are split into chunks processed on [`ComputeTaskPool`] if `parallel` is set.
+pub fn for_each_mut<T, F>(items: &mut [T], parallel: bool, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut T) + Send + Sync,
+{
+    if !split(parallel, items.len()) {
+        items
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, item)| f(i, item));
+        return;
+    }
+    let f = &f;
+    task_pool().scope(|scope| {
+        for (chunk_index, chunk) in items.chunks_mut(CHUNK_SIZE).enumerate() {
+            scope.spawn(async move {
+                for (i, item) in chunk.iter_mut().enumerate() {
+                    f(chunk_index * CHUNK_SIZE + i, item);
+                }
+            });
+        }
+    });
+}
+
+/// Maps every item (with its index) with `f`, keeping the order of items.
+/// Items are split into chunks processed on [`ComputeTaskPool`] if `parallel` is set.
+pub fn map<T, R, F>(items: &[T], parallel: bool, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send + 'static,
+    F: Fn(usize, &T) -> R + Send + Sync,
+{
+    if !split(parallel, items.len()) {
+        return items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| f(i, item))
+            .collect();
+    }
+    let f = &f;
+    task_pool()
+        .scope(|scope| {
+            for (chunk_index, chunk) in items.chunks(CHUNK_SIZE).enumerate() {
+                scope.spawn(async move {
+                    chunk
+                        .iter()
+                        .enumerate()
+                        .map(|(i, item)| f(chunk_index * CHUNK_SIZE + i, item))
+                        .collect::<Vec<R>>()
+                });
+            }
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+}
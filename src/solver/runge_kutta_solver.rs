@@ -0,0 +1,37 @@
+use bevy::prelude::Resource;
+
+use crate::math::Real;
+
+use super::{
+    derivative::{advance, combine, evaluate},
+    state::PhysicsState,
+    SimulationSolver,
+};
+
+/// Classical fourth-order Runge-Kutta method.
+///
+/// Translation, orientation, pulse and angular momentum of all bodies are integrated together.
+/// Forces are re-evaluated at every intermediate state with [`PhysicsState::accumulate_forces`],
+/// so bodies connected with springs see each other's intermediate positions.
+#[derive(Resource)]
+pub struct RungeKuttaSolver {}
+
+impl SimulationSolver for RungeKuttaSolver {
+    fn step(&self, state: &mut PhysicsState, dt: Real) {
+        let initial = state.bodies.clone();
+
+        let k1 = evaluate(state, &initial);
+        advance(state, &initial, &k1, dt / 2.0);
+        let k2 = evaluate(state, &initial);
+        advance(state, &initial, &k2, dt / 2.0);
+        let k3 = evaluate(state, &initial);
+        advance(state, &initial, &k3, dt);
+        let k4 = evaluate(state, &initial);
+
+        let derivatives = combine(
+            &[k1, k2, k3, k4],
+            &[1.0 / 6.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 6.0],
+        );
+        advance(state, &initial, &derivatives, dt);
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::Resource;
+
+use crate::math::Real;
+
+use super::{parallel::for_each_mut, rotate_body, state::PhysicsState, SimulationSolver};
+
+/// Symplectic (semi-implicit) Euler method.
+/// Unlike [`super::euler_solver::EulerSolver`] it updates pulse and angular momentum first
+/// and then moves the body with the **new** velocities, which keeps energy of conservative
+/// systems bounded instead of slowly growing.
+#[derive(Resource)]
+pub struct SemiImplicitEulerSolver {}
+
+impl SimulationSolver for SemiImplicitEulerSolver {
+    fn step(&self, state: &mut PhysicsState, dt: Real) {
+        let implicit_gyroscopic = state.implicit_gyroscopic;
+        for_each_mut(&mut state.bodies, state.parallel, |_, body_state| {
+            let Some(body) = body_state.rigid_body.as_mut() else {
+                return;
+            };
+            let transform = &mut body_state.transform;
+
+            let pulse_derivative = body.force;
+            body.pulse += pulse_derivative * dt;
+
+            let angular_momentum_der = body.torque * dt;
+            body.angular_momentum += angular_momentum_der;
+
+            let position_derivative = body.get_velocity();
+            transform.translation += position_derivative * dt;
+
+            rotate_body(body, transform, dt, implicit_gyroscopic);
+        });
+    }
+}
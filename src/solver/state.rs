@@ -0,0 +1,528 @@
+use super::{
+    exponential_map,
+    parallel::{for_each_mut, map},
+};
+use crate::{
+    body::{Body, BodyType, PhysicsTransform, RigidBody},
+    constraint::{distance::DistanceConstraint, pulley::PulleyConstraint},
+    math::{Real, Vector},
+    settings::SettingsResource,
+    springs::Spring,
+    wind::Wind,
+};
+
+/// State of a single body taking part in a simulation step.
+#[derive(Clone, Copy)]
+pub struct BodyState {
+    pub transform: PhysicsTransform,
+    /// `None` for static and kinematic bodies (e.g. anchors). Solvers never move them.
+    pub rigid_body: Option<RigidBody>,
+    /// Kinematic bodies are moved by [`PhysicsState::prepare_step`]
+    pub body_type: BodyType,
+    /// Force in **World** coordinates that is not re-evaluated by
+    /// [`PhysicsState::accumulate_forces`] (e.g. applied by user systems).
+    pub external_force: Vector,
+    /// Torque in **World** coordinates that is not re-evaluated by
+    /// [`PhysicsState::accumulate_forces`].
+    pub external_torque: Vector,
+}
+
+impl BodyState {
+    /// Creates a state of a body. Current `force` and `torque` of the `rigid_body` are considered
+    /// external.
+    pub fn new(transform: PhysicsTransform, rigid_body: Option<RigidBody>) -> Self {
+        let (external_force, external_torque) = rigid_body
+            .map(|b| (b.force, b.torque))
+            .unwrap_or((Vector::ZERO, Vector::ZERO));
+        let body_type = if rigid_body.is_some() {
+            BodyType::Dynamic
+        } else {
+            BodyType::Static
+        };
+        Self {
+            transform,
+            rigid_body,
+            body_type,
+            external_force,
+            external_torque,
+        }
+    }
+
+    /// Creates a state of a kinematic body moving with `velocity` and `angular_velocity`.
+    pub fn kinematic(
+        transform: PhysicsTransform,
+        velocity: Vector,
+        angular_velocity: Vector,
+    ) -> Self {
+        Self {
+            body_type: BodyType::Kinematic {
+                velocity,
+                angular_velocity,
+            },
+            ..Self::new(transform, None)
+        }
+    }
+
+    /// Velocity of the center of mass of a rigid body or velocity of a kinematic body.
+    pub fn get_velocity(&self) -> Vector {
+        match (self.rigid_body, self.body_type) {
+            (Some(body), _) => body.get_velocity(),
+            (None, BodyType::Kinematic { velocity, .. }) => velocity,
+            _ => Vector::ZERO,
+        }
+    }
+
+    pub fn get_angular_velocity(&self) -> Vector {
+        match (self.rigid_body, self.body_type) {
+            (Some(body), _) => body.get_angular_velocity(&self.transform),
+            (
+                None,
+                BodyType::Kinematic {
+                    angular_velocity, ..
+                },
+            ) => angular_velocity,
+            _ => Vector::ZERO,
+        }
+    }
+
+    /// Velocity of a point given in **Body** coordinates.
+    fn get_point_velocity(&self, point_body: Vector) -> Vector {
+        match self.rigid_body {
+            Some(body) => body.get_particle_body_velocity(point_body, &self.transform),
+            None => {
+                let point = Body.body_to_world_coordinates(point_body, &self.transform);
+                self.get_velocity()
+                    + self
+                        .get_angular_velocity()
+                        .cross(point - self.transform.translation)
+            }
+        }
+    }
+
+    /// Whether the body is kinematic and moves.
+    fn is_moving_kinematic(&self) -> bool {
+        matches!(self.body_type, BodyType::Kinematic { velocity, angular_velocity }
+            if velocity != Vector::ZERO || angular_velocity != Vector::ZERO)
+    }
+}
+
+/// A spring connecting bodies with indices `first` and `second` in [`PhysicsState::bodies`].
+#[derive(Clone, Copy)]
+pub struct SpringState {
+    pub first: usize,
+    pub second: usize,
+    pub spring: Spring,
+}
+
+/// A distance constraint between bodies with indices `first` and `second` in
+/// [`PhysicsState::bodies`].
+#[derive(Clone, Copy)]
+pub struct DistanceConstraintState {
+    pub first: usize,
+    pub second: usize,
+    pub constraint: DistanceConstraint,
+}
+
+/// A pulley constraint between bodies with indices `first` and `second` in
+/// [`PhysicsState::bodies`].
+#[derive(Clone, Copy)]
+pub struct PulleyConstraintState {
+    pub first: usize,
+    pub second: usize,
+    pub constraint: PulleyConstraint,
+    /// Position of the pulley in **World** coordinates
+    pub pulley_position: Vector,
+}
+
+/// A snapshot of everything that is needed to integrate the simulation.
+///
+/// Unlike ECS systems, solvers can change the snapshot and re-evaluate forces as many times as
+/// they want, which is needed by multi-stage methods (e.g. Runge-Kutta).
+pub struct PhysicsState {
+    pub bodies: Vec<BodyState>,
+    pub springs: Vec<SpringState>,
+    pub distance_constraints: Vec<DistanceConstraintState>,
+    pub pulley_constraints: Vec<PulleyConstraintState>,
+    pub gravity: Vector,
+    /// Air that bodies with drag move through
+    pub wind: Wind,
+    /// Number of constraint solver iterations per step
+    pub constraint_iterations: usize,
+    pub baumgarte_constant: Real,
+    /// Whether first-order solvers should integrate rotation with
+    /// [`crate::solver::integrate_rotation_implicit_gyroscopic`]
+    pub implicit_gyroscopic: bool,
+    /// Whether bodies at rest are put to sleep by [`PhysicsState::update_sleeping`]
+    pub allow_sleeping: bool,
+    /// Speed below which a body is considered resting
+    pub sleep_linear_velocity: Real,
+    /// Angular speed below which a body is considered resting
+    pub sleep_angular_velocity: Real,
+    /// For how long all bodies of an island must rest before the island falls asleep
+    pub time_to_sleep: Real,
+    /// Whether bodies and springs are processed on multiple threads.
+    /// Results do not depend on it.
+    pub parallel: bool,
+}
+
+/// Returns mutable references to two different bodies.
+fn pair_mut(
+    bodies: &mut [BodyState],
+    first: usize,
+    second: usize,
+) -> (&mut BodyState, &mut BodyState) {
+    assert_ne!(first, second, "a body can not be constrained to itself");
+    if first < second {
+        let (left, right) = bodies.split_at_mut(second);
+        (&mut left[first], &mut right[0])
+    } else {
+        let (left, right) = bodies.split_at_mut(first);
+        (&mut right[0], &mut left[second])
+    }
+}
+
+impl PhysicsState {
+    /// Creates a state with the parameters of [`SettingsResource::default`].
+    pub fn new(bodies: Vec<BodyState>, springs: Vec<SpringState>, gravity: Vector) -> Self {
+        let settings = SettingsResource::default();
+        Self {
+            bodies,
+            springs,
+            distance_constraints: Vec::new(),
+            pulley_constraints: Vec::new(),
+            gravity,
+            wind: Wind::default(),
+            constraint_iterations: settings.constraints_substeps,
+            baumgarte_constant: settings.baumgarte_constant,
+            implicit_gyroscopic: settings.implicit_gyroscopic_torque,
+            allow_sleeping: settings.allow_sleeping,
+            sleep_linear_velocity: settings.sleep_linear_velocity,
+            sleep_angular_velocity: settings.sleep_angular_velocity,
+            time_to_sleep: settings.time_to_sleep,
+            parallel: settings.parallel,
+        }
+    }
+
+    /// Kinetic energy of rigid bodies.
+    pub fn compute_kinetic_energy(&self) -> Real {
+        self.bodies
+            .iter()
+            .filter_map(|s| {
+                s.rigid_body
+                    .map(|b| b.compute_keenetic_energy(&s.transform))
+            })
+            .sum()
+    }
+
+    /// Energy dissipated by rigid bodies so far, see [`RigidBody::dissipated_energy`].
+    pub fn compute_dissipated_energy(&self) -> Real {
+        self.bodies
+            .iter()
+            .filter_map(|s| s.rigid_body.map(|b| b.dissipated_energy))
+            .sum()
+    }
+
+    /// Sum of kinetic and potential energies of rigid bodies and energies of springs.
+    pub fn compute_energy(&self) -> Real {
+        let bodies_energy: Real = self
+            .bodies
+            .iter()
+            .filter_map(|s| {
+                s.rigid_body
+                    .map(|b| b.compute_energy(&s.transform, self.gravity))
+            })
+            .sum();
+        let springs_energy: Real = self
+            .springs
+            .iter()
+            .map(|s| {
+                let spring = &s.spring;
+                let sp1 = Body.body_to_world_coordinates(
+                    spring.first_body_attachment_point_offset,
+                    &self.bodies[s.first].transform,
+                );
+                let sp2 = Body.body_to_world_coordinates(
+                    spring.second_body_attachment_point_offset,
+                    &self.bodies[s.second].transform,
+                );
+                let delta_length = (sp2 - sp1).length() - spring.rest_length;
+                spring.spring_constant * delta_length.powi(2) / 2.0
+            })
+            .sum();
+        bodies_energy + springs_energy
+    }
+
+    /// Whether a body takes part in a step, i.e. it is a rigid body that is not sleeping.
+    fn is_active(&self, index: usize) -> bool {
+        self.bodies[index]
+            .rigid_body
+            .map(|b| !b.sleeping)
+            .unwrap_or(false)
+    }
+
+    /// Returns pairs of bodies connected with springs and constraints.
+    fn links(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.springs
+            .iter()
+            .map(|s| (s.first, s.second))
+            .chain(
+                self.distance_constraints
+                    .iter()
+                    .map(|c| (c.first, c.second)),
+            )
+            .chain(self.pulley_constraints.iter().map(|c| (c.first, c.second)))
+    }
+
+    /// Splits rigid bodies into islands: groups of bodies connected with springs and
+    /// constraints. Static bodies do not connect islands.
+    /// Returns an island id for every body.
+    fn islands(&self) -> Vec<usize> {
+        fn find(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+
+        let mut parents: Vec<usize> = (0..self.bodies.len()).collect();
+        for (first, second) in self.links() {
+            if self.bodies[first].rigid_body.is_none() || self.bodies[second].rigid_body.is_none() {
+                continue;
+            }
+            let (first, second) = (find(&mut parents, first), find(&mut parents, second));
+            parents[first.max(second)] = first.min(second);
+        }
+        (0..self.bodies.len())
+            .map(|i| find(&mut parents, i))
+            .collect()
+    }
+
+    /// Wakes up sleeping bodies that were disturbed: got a force, a torque or a pulse
+    /// (e.g. from a user system), are pushed by the wind, or are connected to an awake body or a
+    /// moving kinematic one. Every body wakes up if sleeping is not allowed.
+    pub fn wake_up_bodies(&mut self) {
+        let mut disturbed_islands = Vec::new();
+        let islands = self.islands();
+        for (state, island) in self.bodies.iter().zip(islands.iter()) {
+            let Some(body) = state.rigid_body else {
+                continue;
+            };
+            let disturbed = !body.sleeping
+                || state.external_force != Vector::ZERO
+                || state.external_torque != Vector::ZERO
+                || body.pulse != Vector::ZERO
+                || body.angular_momentum != Vector::ZERO
+                || body.is_in_wind(&state.transform, &self.wind);
+            if disturbed || !self.allow_sleeping {
+                disturbed_islands.push(*island);
+            }
+        }
+        for (first, second) in self.links() {
+            if self.bodies[first].is_moving_kinematic() {
+                disturbed_islands.push(islands[second]);
+            }
+            if self.bodies[second].is_moving_kinematic() {
+                disturbed_islands.push(islands[first]);
+            }
+        }
+        disturbed_islands.sort_unstable();
+
+        for (state, island) in self.bodies.iter_mut().zip(islands.iter()) {
+            if let Some(body) = state.rigid_body.as_mut() {
+                if body.sleeping && disturbed_islands.binary_search(island).is_ok() {
+                    body.sleeping = false;
+                    body.resting_time = 0.0;
+                }
+            }
+        }
+    }
+
+    /// Updates resting time of awake bodies after a step of length `dt` and puts islands whose
+    /// bodies have been resting for [`PhysicsState::time_to_sleep`] to sleep.
+    pub fn update_sleeping(&mut self, dt: Real) {
+        if !self.allow_sleeping {
+            return;
+        }
+        let mut awake_islands = Vec::new();
+        let islands = self.islands();
+        for (state, island) in self.bodies.iter_mut().zip(islands.iter()) {
+            let transform = state.transform;
+            let Some(body) = state.rigid_body.as_mut() else {
+                continue;
+            };
+            if body.sleeping {
+                continue;
+            }
+            // A body pushed by the wind would be woken up right away
+            let resting = body.get_velocity().length() < self.sleep_linear_velocity
+                && body.get_angular_velocity(&transform).length() < self.sleep_angular_velocity
+                && !body.is_in_wind(&transform, &self.wind);
+            body.resting_time = if resting { body.resting_time + dt } else { 0.0 };
+            if body.resting_time < self.time_to_sleep {
+                awake_islands.push(*island);
+            }
+        }
+        awake_islands.sort_unstable();
+
+        for (state, island) in self.bodies.iter_mut().zip(islands.iter()) {
+            if let Some(body) = state.rigid_body.as_mut() {
+                if !body.sleeping && awake_islands.binary_search(island).is_err() {
+                    body.sleeping = true;
+                    // The body stops, so its remaining kinetic energy is lost
+                    body.dissipated_energy += body.compute_keenetic_energy(&state.transform);
+                    body.pulse = Vector::ZERO;
+                    body.angular_momentum = Vector::ZERO;
+                }
+            }
+        }
+    }
+
+    /// Temporarily turns sleeping bodies into static ones, so that solvers skip them.
+    /// Returns them to be put back with [`PhysicsState::restore_sleeping_bodies`].
+    pub fn take_sleeping_bodies(&mut self) -> Vec<(usize, RigidBody)> {
+        let mut sleeping = Vec::new();
+        for (i, state) in self.bodies.iter_mut().enumerate() {
+            if let Some(body) = state.rigid_body.filter(|b| b.sleeping) {
+                sleeping.push((i, body));
+                state.rigid_body = None;
+            }
+        }
+        sleeping
+    }
+
+    pub fn restore_sleeping_bodies(&mut self, sleeping: Vec<(usize, RigidBody)>) {
+        for (i, body) in sleeping {
+            self.bodies[i].rigid_body = Some(body);
+        }
+    }
+
+    /// Prepares the state for a step of length `dt`: accumulates forces, records energy
+    /// dissipated by damping and drag during the step, solves constraints and then moves
+    /// kinematic bodies to where they are at the end of the step.
+    pub fn prepare_step(&mut self, dt: Real) {
+        self.accumulate_forces();
+        self.dissipate_energy(dt);
+        self.solve_constraints(dt);
+        self.move_kinematic_bodies(dt);
+    }
+
+    /// Adds energy that damping and drag of every rigid body dissipate during `dt` to its
+    /// `dissipated_energy`. The power is taken at the beginning of the step.
+    pub fn dissipate_energy(&mut self, dt: Real) {
+        for state in self.bodies.iter_mut() {
+            if let Some(body) = state.rigid_body.as_mut() {
+                let drag_power = -body
+                    .compute_drag_force(&state.transform, &self.wind)
+                    .dot(body.get_velocity());
+                body.dissipated_energy +=
+                    (body.compute_damping_power(&state.transform) + drag_power) * dt;
+            }
+        }
+    }
+
+    /// Moves kinematic bodies with their velocities during `dt`.
+    pub fn move_kinematic_bodies(&mut self, dt: Real) {
+        for state in self.bodies.iter_mut() {
+            if let BodyType::Kinematic {
+                velocity,
+                angular_velocity,
+            } = state.body_type
+            {
+                let transform = &mut state.transform;
+                transform.translation += velocity * dt;
+                transform.rotation =
+                    (exponential_map(angular_velocity, dt) * transform.rotation).normalize();
+            }
+        }
+    }
+
+    /// Applies constraint impulses to bodies so that constraints hold after a step of length `dt`.
+    pub fn solve_constraints(&mut self, dt: Real) {
+        if self.constraint_iterations == 0 {
+            return;
+        }
+        let constraint_dt = dt / self.constraint_iterations as Real;
+        for _ in 0..self.constraint_iterations {
+            for c in self.distance_constraints.iter() {
+                if !self.is_active(c.first) && !self.is_active(c.second) {
+                    continue;
+                }
+                let (first, second) = pair_mut(&mut self.bodies, c.first, c.second);
+                c.constraint
+                    .solve(first, second, self.baumgarte_constant, constraint_dt);
+            }
+            for c in self.pulley_constraints.iter() {
+                if !self.is_active(c.first) && !self.is_active(c.second) {
+                    continue;
+                }
+                let (first, second) = pair_mut(&mut self.bodies, c.first, c.second);
+                c.constraint.solve(
+                    first,
+                    second,
+                    c.pulley_position,
+                    self.baumgarte_constant,
+                    constraint_dt,
+                );
+            }
+        }
+    }
+
+    /// Recomputes `force` and `torque` of every rigid body from its current state.
+    /// Result is a sum of external forces, gravity, damping, drag and spring forces.
+    ///
+    /// Spring forces are computed in parallel and then applied one by one in the order of
+    /// springs, so the result does not depend on [`PhysicsState::parallel`].
+    pub fn accumulate_forces(&mut self) {
+        let gravity = self.gravity;
+        let wind = &self.wind;
+        for_each_mut(&mut self.bodies, self.parallel, |_, state| {
+            if let Some(body) = state.rigid_body.as_mut() {
+                body.force = state.external_force;
+                body.torque = state.external_torque;
+                // Gravity acts at the center of mass, so it makes no torque
+                body.force += gravity * body.mass;
+                body.apply_damping(&state.transform);
+                let drag_force = body.compute_drag_force(&state.transform, wind);
+                let center_of_mass = body.get_center_of_mass(&state.transform);
+                body.apply_force(&state.transform, center_of_mass, drag_force);
+            }
+        });
+
+        let spring_forces = map(&self.springs, self.parallel, |_, spring_state| {
+            if !self.is_active(spring_state.first) && !self.is_active(spring_state.second) {
+                return None;
+            }
+            let spring = &spring_state.spring;
+            let b1 = &self.bodies[spring_state.first];
+            let b2 = &self.bodies[spring_state.second];
+
+            let sp1 = Body.body_to_world_coordinates(
+                spring.first_body_attachment_point_offset,
+                &b1.transform,
+            );
+            let sp2 = Body.body_to_world_coordinates(
+                spring.second_body_attachment_point_offset,
+                &b2.transform,
+            );
+            let v1 = b1.get_point_velocity(spring.first_body_attachment_point_offset);
+            let v2 = b2.get_point_velocity(spring.second_body_attachment_point_offset);
+
+            Some((sp1, sp2, spring.compute_force(sp1, v1, sp2, v2)))
+        });
+
+        for (spring_state, spring_force) in self.springs.iter().zip(spring_forces) {
+            let Some((sp1, sp2, force)) = spring_force else {
+                continue;
+            };
+            let first = &mut self.bodies[spring_state.first];
+            if let Some(body) = first.rigid_body.as_mut() {
+                body.apply_force(&first.transform, sp1, force);
+            }
+            let second = &mut self.bodies[spring_state.second];
+            if let Some(body) = second.rigid_body.as_mut() {
+                body.apply_force(&second.transform, sp2, -force);
+            }
+        }
+    }
+}
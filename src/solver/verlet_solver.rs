@@ -0,0 +1,46 @@
+use bevy::prelude::Resource;
+
+use crate::math::Real;
+
+use super::{
+    parallel::for_each_mut,
+    rotate_body,
+    state::{BodyState, PhysicsState},
+    SimulationSolver,
+};
+
+/// Velocity Verlet (leapfrog) method.
+///
+/// Every step is "kick-drift-kick": half of the impulse is applied with forces at the beginning
+/// of the step, bodies are moved with the half-step velocities, forces are re-evaluated at the
+/// new positions and the second half of the impulse is applied.
+#[derive(Resource)]
+pub struct VerletSolver {}
+
+impl SimulationSolver for VerletSolver {
+    fn step(&self, state: &mut PhysicsState, dt: Real) {
+        let implicit_gyroscopic = state.implicit_gyroscopic;
+
+        let kick = |_, body_state: &mut BodyState| {
+            if let Some(body) = body_state.rigid_body.as_mut() {
+                body.pulse += body.force * dt * 0.5;
+                body.angular_momentum += body.torque * dt * 0.5;
+            }
+        };
+
+        for_each_mut(&mut state.bodies, state.parallel, kick);
+
+        // Drift
+        for_each_mut(&mut state.bodies, state.parallel, |_, body_state| {
+            let Some(body) = body_state.rigid_body.as_mut() else {
+                return;
+            };
+            let transform = &mut body_state.transform;
+            transform.translation += body.get_velocity() * dt;
+            rotate_body(body, transform, dt, implicit_gyroscopic);
+        });
+
+        state.accumulate_forces();
+        for_each_mut(&mut state.bodies, state.parallel, kick);
+    }
+}
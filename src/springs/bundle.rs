@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::energy::Energy;
+use crate::{
+    energy::Energy,
+    math::{Real, Vector},
+};
 
 use super::Spring;
 
@@ -14,12 +17,12 @@ pub struct SpringBundle {
 impl SpringBundle {
     pub fn new(
         first_body: Entity,
-        first_body_point: Vec3,
+        first_body_point: Vector,
         second_body: Entity,
-        second_body_point: Vec3,
-        rest_length: f32,
-        spring_constant: f32,
-        damping_constant: f32,
+        second_body_point: Vector,
+        rest_length: Real,
+        spring_constant: Real,
+        damping_constant: Real,
         pbr_bundle: PbrBundle,
     ) -> Self {
         Self {
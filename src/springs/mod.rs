@@ -1,24 +1,79 @@
 use bevy::prelude::*;
 
+use crate::math::{Matrix, Real, Vector};
+
 pub mod bundle;
 pub mod systems;
 
 /// A Spring component.
-/// It is considered that spring is attached right to the center of a body.
-#[derive(Component)]
+/// A spring is attached to points given in **Body** coordinates, i.e. relative to the origin of
+/// a body's transform. The origin is not the center of mass if the body has
+/// [`crate::body::RigidBody::center_of_mass`] set.
+#[derive(Component, Clone, Copy)]
 pub struct Spring {
     /// First body that spring is attached to
     pub first_body: Entity,
-    /// Offset of a point where spring is attached relative to center of mass
-    pub first_body_attachment_point_offset: Vec3,
+    /// Point where spring is attached in **Body** coordinates of the first body
+    pub first_body_attachment_point_offset: Vector,
     /// Second body that spring is attached to
     pub second_body: Entity,
-    /// Offset of a point where spring is attached relative to center of mass
-    pub second_body_attachment_point_offset: Vec3,
+    /// Point where spring is attached in **Body** coordinates of the second body
+    pub second_body_attachment_point_offset: Vector,
     /// Spring length in the state of rest
-    pub rest_length: f32,
+    pub rest_length: Real,
     /// Spring constant of a spring
-    pub spring_constant: f32,
+    pub spring_constant: Real,
     /// Damping constant of a spring
-    pub damping_constant: f32,
+    pub damping_constant: Real,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            first_body: Entity::PLACEHOLDER,
+            first_body_attachment_point_offset: Vector::ZERO,
+            second_body: Entity::PLACEHOLDER,
+            second_body_attachment_point_offset: Vector::ZERO,
+            rest_length: 1.0,
+            spring_constant: 1.0,
+            damping_constant: 0.0,
+        }
+    }
+}
+
+impl Spring {
+    /// Computes the force (Hooke's law and damping) acting on the **first** body.
+    /// The second body is affected by the opposite force.
+    /// Attachment points `sp1`, `sp2` and their velocities `v1`, `v2` are given in **World**
+    /// coordinates.
+    pub fn compute_force(&self, sp1: Vector, v1: Vector, sp2: Vector, v2: Vector) -> Vector {
+        let spring_velocity = v1 - v2;
+
+        let spring_length_vec = sp1 - sp2;
+        let spring_length = spring_length_vec.length();
+        let spring_length_norm = spring_length_vec.normalize();
+
+        -(self.spring_constant * (spring_length - self.rest_length)
+            + self.damping_constant * spring_velocity * spring_length_norm)
+            * spring_length_norm
+    }
+
+    /// Computes Jacobians of the force acting on the **first** body with respect to position
+    /// (stiffness) and velocity (damping) of the first attachment point.
+    /// Jacobians with respect to the second attachment point have the opposite sign.
+    ///
+    /// Both matrices are negative semi-definite (compressed springs are not allowed to make
+    /// the stiffness matrix indefinite), which is required by implicit solvers.
+    pub fn compute_jacobians(&self, sp1: Vector, sp2: Vector) -> (Matrix, Matrix) {
+        let spring_length_vec = sp1 - sp2;
+        let spring_length = spring_length_vec.length();
+        let n = spring_length_vec.normalize();
+        let nnt = Matrix::from_cols(n * n.x, n * n.y, n * n.z);
+
+        let transverse_koef = (1.0 - self.rest_length / spring_length).max(0.0);
+        let stiffness = -self.spring_constant * (nnt + transverse_koef * (Matrix::IDENTITY - nnt));
+        let damping = -self.damping_constant * Matrix::from_diagonal(n * n);
+
+        (stiffness, damping)
+    }
 }
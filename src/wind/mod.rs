@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::math::{Real, Vector};
+
+/// Air the bodies move through. Bodies with [`crate::body::drag::Drag`] are pushed by the wind
+/// and slowed down by still air.
+///
+/// The wind is either uniform or a field that gives air velocity at every point.
+#[derive(Resource, Clone)]
+pub struct Wind {
+    /// Density of air, kg/m³
+    pub air_density: Real,
+    velocity: Vector,
+    field: Option<Arc<dyn Fn(Vector) -> Vector + Send + Sync>>,
+}
+
+impl Default for Wind {
+    fn default() -> Self {
+        Self::uniform(Vector::ZERO)
+    }
+}
+
+impl Wind {
+    /// Creates a wind blowing with the same `velocity` everywhere.
+    pub fn uniform(velocity: Vector) -> Self {
+        Self {
+            air_density: 1.225,
+            velocity,
+            field: None,
+        }
+    }
+
+    /// Creates a spatially-varying wind. `field` returns air velocity at a point, both are given
+    /// in **World** coordinates.
+    pub fn field(field: impl Fn(Vector) -> Vector + Send + Sync + 'static) -> Self {
+        Self {
+            field: Some(Arc::new(field)),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the wind with given `air_density`.
+    pub fn with_air_density(self, air_density: Real) -> Self {
+        Self {
+            air_density,
+            ..self
+        }
+    }
+
+    /// Returns velocity of air at a `point` in **World** coordinates.
+    pub fn velocity_at(&self, point: Vector) -> Vector {
+        match &self.field {
+            Some(field) => field(point),
+            None => self.velocity,
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy::prelude::*;
+
+use crate::{
+    body::{BodyType, PhysicsTransform, RigidBody},
+    clock::SimulationClock,
+    constraint::{distance::DistanceConstraint, pulley::PulleyConstraint},
+    energy::EnergyProjection,
+    math::{Real, Vector},
+    settings::SettingsResource,
+    solver::{
+        integrate_state,
+        state::{
+            BodyState, DistanceConstraintState, PhysicsState, PulleyConstraintState, SpringState,
+        },
+    },
+    springs::Spring,
+    wind::Wind,
+};
+
+/// Source of unique ids of worlds, so that handles of one world are rejected by another.
+static NEXT_WORLD_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A handle of a body added to a [`PhysicsWorld`].
+///
+/// A handle is valid only in the world that returned it. Methods of [`PhysicsWorld`] panic if
+/// they are given a handle of another world.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BodyHandle {
+    world: usize,
+    index: usize,
+}
+
+impl BodyHandle {
+    /// Index of the body in [`PhysicsState::bodies`].
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// A simulation that runs without Bevy `App`.
+///
+/// It uses the same state, solvers and settings as [`crate::PDRustPlugin`], so it is suitable for
+/// tests and command line tools. Springs and constraints added to the world do not refer to any
+/// entity, their bodies are given with [`BodyHandle`]s.
+pub struct PhysicsWorld {
+    /// Integration method, substeps, constraint parameters and gravity of the world.
+    /// `slow_motion_koef`, `print_energy_in_console` and `deterministic` are ignored: the world
+    /// always steps by the given `dt` and processes everything in the order it was added.
+    pub settings: SettingsResource,
+    id: usize,
+    state: PhysicsState,
+    clock: SimulationClock,
+    energy_projection: EnergyProjection,
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new(SettingsResource::default())
+    }
+}
+
+impl PhysicsWorld {
+    pub fn new(settings: SettingsResource) -> Self {
+        let state = PhysicsState::new(Vec::new(), Vec::new(), settings.gravity_vector);
+        Self {
+            settings,
+            id: NEXT_WORLD_ID.fetch_add(1, Ordering::Relaxed),
+            state,
+            clock: SimulationClock::default(),
+            energy_projection: EnergyProjection::default(),
+        }
+    }
+
+    /// Adds a rigid body. `transform` is either a `Transform` or a [`PhysicsTransform`].
+    pub fn add_body(
+        &mut self,
+        transform: impl Into<PhysicsTransform>,
+        rigid_body: RigidBody,
+    ) -> BodyHandle {
+        self.push_body(BodyState::new(transform.into(), Some(rigid_body)))
+    }
+
+    /// Adds a static body (e.g. an anchor for springs). Static bodies never move.
+    pub fn add_static_body(&mut self, transform: impl Into<PhysicsTransform>) -> BodyHandle {
+        self.push_body(BodyState::new(transform.into(), None))
+    }
+
+    /// Adds a kinematic body moving with `velocity` and `angular_velocity` in **World**
+    /// coordinates, see [`BodyType::Kinematic`].
+    pub fn add_kinematic_body(
+        &mut self,
+        transform: impl Into<PhysicsTransform>,
+        velocity: Vector,
+        angular_velocity: Vector,
+    ) -> BodyHandle {
+        self.push_body(BodyState::kinematic(
+            transform.into(),
+            velocity,
+            angular_velocity,
+        ))
+    }
+
+    fn push_body(&mut self, body_state: BodyState) -> BodyHandle {
+        self.state.bodies.push(body_state);
+        self.energy_projection.reset();
+        BodyHandle {
+            world: self.id,
+            index: self.state.bodies.len() - 1,
+        }
+    }
+
+    /// Returns the index of a body in the state.
+    ///
+    /// # Panics
+    /// Panics if the handle was returned by another world.
+    fn index(&self, body: BodyHandle) -> usize {
+        assert_eq!(
+            body.world, self.id,
+            "the body handle belongs to another world"
+        );
+        body.index
+    }
+
+    /// Connects two bodies with a `spring`. Its bodies are given with handles, so `first_body`
+    /// and `second_body` entities of the `spring` are ignored.
+    pub fn add_spring(&mut self, first_body: BodyHandle, second_body: BodyHandle, spring: Spring) {
+        self.state.springs.push(SpringState {
+            first: self.index(first_body),
+            second: self.index(second_body),
+            spring,
+        });
+        self.energy_projection.reset();
+    }
+
+    /// Keeps distance between two points of bodies in `[min_distance, max_distance]`.
+    /// Offsets are given in **Body** coordinates.
+    pub fn add_distance_constraint(
+        &mut self,
+        first_body: BodyHandle,
+        second_body: BodyHandle,
+        first_body_offset: Vector,
+        second_body_offset: Vector,
+        min_distance: Real,
+        max_distance: Real,
+    ) {
+        self.state
+            .distance_constraints
+            .push(DistanceConstraintState {
+                first: self.index(first_body),
+                second: self.index(second_body),
+                constraint: DistanceConstraint::new(
+                    Entity::PLACEHOLDER,
+                    Entity::PLACEHOLDER,
+                    first_body_offset,
+                    second_body_offset,
+                    min_distance,
+                    max_distance,
+                ),
+            });
+    }
+
+    /// Connects two bodies with a rope of length `max_distance` going through a pulley at
+    /// `pulley_position`. Offsets are given in **Body** coordinates.
+    pub fn add_pulley_constraint(
+        &mut self,
+        first_body: BodyHandle,
+        second_body: BodyHandle,
+        first_body_offset: Vector,
+        second_body_offset: Vector,
+        max_distance: Real,
+        pulley_position: Vector,
+    ) {
+        self.state.pulley_constraints.push(PulleyConstraintState {
+            first: self.index(first_body),
+            second: self.index(second_body),
+            constraint: PulleyConstraint::new(
+                Entity::PLACEHOLDER,
+                Entity::PLACEHOLDER,
+                first_body_offset,
+                second_body_offset,
+                max_distance,
+            ),
+            pulley_position,
+        });
+    }
+
+    /// Sets velocities of a kinematic body in **World** coordinates.
+    pub fn set_kinematic_velocity(
+        &mut self,
+        body: BodyHandle,
+        velocity: Vector,
+        angular_velocity: Vector,
+    ) {
+        self.kinematic_body_mut(body).body_type = BodyType::Kinematic {
+            velocity,
+            angular_velocity,
+        };
+    }
+
+    /// Sets velocities of a kinematic body so that it reaches `target` after the next step of
+    /// length `dt`. Call it before every step to make a body follow a path.
+    pub fn move_kinematic_body_to(
+        &mut self,
+        body: BodyHandle,
+        target: impl Into<PhysicsTransform>,
+        dt: Real,
+    ) {
+        let body_state = self.kinematic_body_mut(body);
+        body_state.body_type =
+            BodyType::kinematic_towards(&body_state.transform, &target.into(), dt);
+    }
+
+    fn kinematic_body_mut(&mut self, body: BodyHandle) -> &mut BodyState {
+        let index = self.index(body);
+        let body_state = &mut self.state.bodies[index];
+        assert!(
+            matches!(body_state.body_type, BodyType::Kinematic { .. }),
+            "only kinematic bodies have prescribed velocities"
+        );
+        body_state
+    }
+
+    /// Sets force and torque in **World** coordinates that act on a body until they are changed.
+    pub fn set_external_force(&mut self, body: BodyHandle, force: Vector, torque: Vector) {
+        let index = self.index(body);
+        let body_state = &mut self.state.bodies[index];
+        body_state.external_force = force;
+        body_state.external_torque = torque;
+    }
+
+    /// Sets the air that bodies with drag move through.
+    pub fn set_wind(&mut self, wind: Wind) {
+        self.state.wind = wind;
+    }
+
+    /// Advances the world by `dt` and returns the number of steps made by the solver.
+    pub fn step(&mut self, dt: Real) -> usize {
+        self.clock.advance(dt);
+        self.state.gravity = self.settings.gravity_vector;
+        if self.settings.energy_projection {
+            self.energy_projection.start(&self.state);
+        } else {
+            self.energy_projection.reset();
+        }
+        let steps = integrate_state(&mut self.state, &self.settings, dt);
+        if self.settings.energy_projection {
+            self.energy_projection.apply(&mut self.state);
+        }
+        steps
+    }
+
+    pub fn transform(&self, body: BodyHandle) -> PhysicsTransform {
+        self.state.bodies[self.index(body)].transform
+    }
+
+    /// Returns `None` for static bodies.
+    pub fn rigid_body(&self, body: BodyHandle) -> Option<&RigidBody> {
+        self.state.bodies[self.index(body)].rigid_body.as_ref()
+    }
+
+    /// Gives access to a body, e.g. to teleport it or change its momentum between steps.
+    /// Energy the body gets this way is kept by the energy projection.
+    pub fn body_mut(&mut self, body: BodyHandle) -> &mut BodyState {
+        let index = self.index(body);
+        self.energy_projection.reset();
+        &mut self.state.bodies[index]
+    }
+
+    pub fn state(&self) -> &PhysicsState {
+        &self.state
+    }
+
+    pub fn clock(&self) -> &SimulationClock {
+        &self.clock
+    }
+
+    pub fn energy_projection(&self) -> &EnergyProjection {
+        &self.energy_projection
+    }
+
+    /// Sum of kinetic and potential energies of rigid bodies and energies of springs.
+    pub fn compute_energy(&self) -> Real {
+        self.state.compute_energy()
+    }
+
+    /// Energy dissipated by rigid bodies so far, see [`RigidBody::dissipated_energy`].
+    pub fn compute_dissipated_energy(&self) -> Real {
+        self.state.compute_dissipated_energy()
+    }
+}
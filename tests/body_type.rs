@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{Body, BodyType, PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    world::PhysicsWorld,
+    PDRustPlugin,
+};
+
+/// A kinematic pivot moving along a path drags a pendulum hanging on it, and is not affected
+/// by gravity or by the pendulum. A bob on a spring that rises together with its pivot keeps
+/// hanging at the same distance, since nothing pulls it.
+#[test]
+fn kinematic_pivot_drags_pendulum() {
+    for implicit in [false, true] {
+        let method = || {
+            if implicit {
+                IntergrationMethod::ImplicitEuler
+            } else {
+                IntergrationMethod::SemiImplicitEuler
+            }
+        };
+        let mut world = PhysicsWorld::new(SettingsResource {
+            method: method(),
+            ..default()
+        });
+        let pivot =
+            world.add_kinematic_body(PhysicsTransform::IDENTITY, Vector::ZERO, Vector::ZERO);
+        let bob = world.add_body(
+            PhysicsTransform::from_xyz(0.0, -1.0, 0.0),
+            RigidBody::new_sphere(1.0, 0.1, Vector::ZERO, Vector::ZERO),
+        );
+        world.add_distance_constraint(pivot, bob, Vector::ZERO, Vector::ZERO, 1.0, 1.0);
+
+        let dt: Real = 1.0 / 60.0;
+        for step in 1..=60 {
+            let target = PhysicsTransform::from_xyz(step as Real * dt, 0.0, 0.0);
+            world.move_kinematic_body_to(pivot, target, dt);
+            world.step(dt);
+        }
+
+        let pivot_position = world.transform(pivot).translation;
+        assert!((pivot_position - Vector::new(1.0, 0.0, 0.0)).length() < 1e-4);
+        // The bob lags behind the pivot but follows it
+        let bob_position = world.transform(bob).translation;
+        assert!(
+            bob_position.x > 0.1 && bob_position.x < 1.0,
+            "bob at {bob_position}"
+        );
+        assert!(((bob_position - pivot_position).length() - 1.0).abs() < 1e-2);
+
+        let mut world = PhysicsWorld::new(SettingsResource {
+            method: method(),
+            gravity_vector: Vector::ZERO,
+            ..default()
+        });
+        let velocity = Vector::new(0.0, 1.0, 0.0);
+        let pivot = world.add_kinematic_body(PhysicsTransform::IDENTITY, velocity, Vector::ZERO);
+        let bob = world.add_body(
+            PhysicsTransform::from_xyz(0.0, -1.0, 0.0),
+            RigidBody::new_sphere(1.0, 0.1, velocity, Vector::ZERO),
+        );
+        world.add_spring(
+            bob,
+            pivot,
+            Spring {
+                spring_constant: 1e4,
+                ..default()
+            },
+        );
+        for _ in 0..60 {
+            world.step(dt);
+        }
+        let offset = world.transform(bob).translation - world.transform(pivot).translation;
+        assert!(
+            (offset - Vector::new(0.0, -1.0, 0.0)).length() < 1e-4,
+            "bob at {offset} from the rising pivot"
+        );
+    }
+}
+
+/// Static and kinematic body types override rigid bodies in the ECS simulation.
+#[test]
+fn body_types_in_plugin() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(PDRustPlugin);
+    let rigid_body = RigidBody::new_sphere(1.0, 0.1, Vector::ZERO, Vector::ZERO);
+    let static_body = app
+        .world
+        .spawn((Transform::IDENTITY, Body, BodyType::Static, rigid_body))
+        .id();
+    let dynamic_body = app
+        .world
+        .spawn((Transform::IDENTITY, Body, rigid_body))
+        .id();
+    let kinematic_body = app
+        .world
+        .spawn((
+            Transform::IDENTITY,
+            Body,
+            BodyType::Kinematic {
+                velocity: Vector::new(1.0, 0.0, 0.0),
+                angular_velocity: Vector::new(0.0, 1.0, 0.0),
+            },
+        ))
+        .id();
+    app.update();
+    for _ in 0..10 {
+        app.world.run_schedule(FixedUpdate);
+    }
+
+    let translation = |entity| {
+        app.world
+            .get::<PhysicsTransform>(entity)
+            .unwrap()
+            .translation
+    };
+    assert_eq!(translation(static_body), Vector::ZERO);
+    assert!(translation(dynamic_body).y < 0.0);
+    assert!(translation(kinematic_body).x > 0.0);
+    assert_eq!(translation(kinematic_body).y, 0.0);
+    let rotation = app
+        .world
+        .get::<PhysicsTransform>(kinematic_body)
+        .unwrap()
+        .rotation;
+    assert!(rotation.to_scaled_axis().y > 0.0);
+}
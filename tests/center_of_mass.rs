@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::Vector,
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    world::PhysicsWorld,
+};
+
+fn methods() -> Vec<IntergrationMethod> {
+    vec![
+        IntergrationMethod::EulerMethod,
+        IntergrationMethod::SemiImplicitEuler,
+        IntergrationMethod::VelocityVerlet,
+        IntergrationMethod::RungeKutta4,
+        IntergrationMethod::ImplicitEuler,
+        IntergrationMethod::DormandPrince,
+    ]
+}
+
+/// A free body spins around its center of mass, not around its origin.
+#[test]
+fn free_body_spins_around_center_of_mass() {
+    let center_of_mass = Vector::new(1.0, 0.0, 0.0);
+    for method in methods() {
+        let mut world = PhysicsWorld::new(SettingsResource {
+            method,
+            gravity_vector: Vector::ZERO,
+            ..default()
+        });
+        let body = world.add_body(
+            PhysicsTransform::IDENTITY,
+            RigidBody::new_sphere(1.0, 0.5, Vector::ZERO, Vector::new(0.0, 0.0, 0.5))
+                .with_center_of_mass(center_of_mass),
+        );
+        for _ in 0..120 {
+            world.step(1.0 / 60.0);
+        }
+
+        let transform = world.transform(body);
+        let rigid_body = world.rigid_body(body).unwrap();
+        let drift = (rigid_body.get_center_of_mass(&transform) - center_of_mass).length();
+        assert!(drift < 1e-4, "center of mass moved by {drift}");
+        // The origin went around the center of mass
+        assert!(transform.translation.length() > 0.5);
+    }
+}
+
+/// Moving the origin of a body away from its center of mass (and shifting spring anchors
+/// accordingly) does not change its motion.
+#[test]
+fn spring_anchors_are_relative_to_origin() {
+    let offset = Vector::new(0.3, -0.2, 0.1);
+    let simulate = |center_of_mass: Vector| {
+        let mut world = PhysicsWorld::new(SettingsResource {
+            method: IntergrationMethod::RungeKutta4,
+            ..default()
+        });
+        let anchor = world.add_static_body(PhysicsTransform::IDENTITY);
+        let body = world.add_body(
+            PhysicsTransform::from_translation(Vector::new(1.0, -1.0, 0.0) - center_of_mass),
+            RigidBody::new_box(1.0, 1.0, 0.5, 0.25, Vector::ZERO, Vector::ZERO)
+                .with_center_of_mass(center_of_mass),
+        );
+        world.add_spring(
+            body,
+            anchor,
+            Spring {
+                first_body_attachment_point_offset: center_of_mass + Vector::new(0.5, 0.25, 0.0),
+                spring_constant: 30.0,
+                ..default()
+            },
+        );
+        for _ in 0..300 {
+            world.step(1.0 / 60.0);
+        }
+        let transform = world.transform(body);
+        let rigid_body = world.rigid_body(body).unwrap();
+        (
+            rigid_body.get_center_of_mass(&transform),
+            transform.rotation,
+        )
+    };
+
+    let (expected_center, expected_rotation) = simulate(Vector::ZERO);
+    let (center, rotation) = simulate(offset);
+    assert!((center - expected_center).length() < 1e-3);
+    assert!(rotation.angle_between(expected_rotation) < 1e-3);
+}
@@ -0,0 +1,13 @@
+use pdrust::math::{Matrix, Real};
+
+/// Asserts that inertia tensors are equal up to a relative error of `1e-5`.
+pub fn assert_tensor_eq(actual: Matrix, expected: Matrix) {
+    let scale = expected
+        .to_cols_array()
+        .iter()
+        .fold(1.0, |m: Real, x| m.max(x.abs()));
+    assert!(
+        actual.abs_diff_eq(expected, 1e-5 * scale),
+        "expected {expected}, got {actual}"
+    );
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::assert_tensor_eq;
+use pdrust::{
+    body::{compound::CompoundBody, PhysicsTransform, RigidBody},
+    math::{consts::FRAC_PI_2, Matrix, Quaternion, Vector},
+};
+
+/// Two spheres connected by a rod along X.
+#[test]
+fn dumbbell_matches_parallel_axis_theorem() {
+    let (sphere_mass, r, rod_mass, d) = (2.0, 0.25, 1.0, 1.5);
+    let sphere = RigidBody::new_sphere(sphere_mass, r, Vector::ZERO, Vector::ZERO);
+    let rod = RigidBody::new_rod(rod_mass, 2.0 * d, Vector::ZERO, Vector::ZERO);
+    let dumbbell = CompoundBody::new()
+        .with_part(sphere, PhysicsTransform::from_xyz(-d, 0.0, 0.0))
+        .with_part(sphere, PhysicsTransform::from_xyz(d, 0.0, 0.0))
+        // The rod lies along Y, turn it to X
+        .with_part(
+            rod,
+            PhysicsTransform::from_rotation(Quaternion::from_rotation_z(FRAC_PI_2)),
+        );
+
+    let properties = dumbbell.mass_properties().unwrap();
+
+    let sphere_inertia = 2.0 / 5.0 * sphere_mass * r * r;
+    let lateral =
+        2.0 * (sphere_inertia + sphere_mass * d * d) + rod_mass * (2.0 * d).powi(2) / 12.0;
+    assert!((properties.mass - (2.0 * sphere_mass + rod_mass)).abs() < 1e-5);
+    assert!(properties.center_of_mass.length() < 1e-5);
+    assert_tensor_eq(
+        properties.inertia_tensor,
+        Matrix::from_diagonal(Vector::new(2.0 * sphere_inertia, lateral, lateral)),
+    );
+}
+
+/// A pendulum with a bob hanging below the origin of the compound.
+#[test]
+fn pendulum_center_of_mass_is_shifted_to_bob() {
+    let (bob_mass, rod_mass, length) = (3.0, 1.0, 2.0);
+    let pendulum = CompoundBody::new()
+        .with_part(
+            RigidBody::new_sphere(bob_mass, 0.1, Vector::ZERO, Vector::ZERO),
+            PhysicsTransform::from_xyz(0.0, -length, 0.0),
+        )
+        .with_part(
+            RigidBody::new_rod(rod_mass, length, Vector::ZERO, Vector::ZERO),
+            PhysicsTransform::from_xyz(0.0, -length / 2.0, 0.0),
+        );
+
+    let properties = pendulum.mass_properties().unwrap();
+
+    let mass = bob_mass + rod_mass;
+    let center = -(bob_mass * length + rod_mass * length / 2.0) / mass;
+    assert!((pendulum.center_of_mass().unwrap() - Vector::new(0.0, center, 0.0)).length() < 1e-5);
+
+    // Inertia around the pivot, moved back to the center of mass
+    let pivot_inertia =
+        2.0 / 5.0 * bob_mass * 0.01 + bob_mass * length * length + rod_mass * length * length / 3.0;
+    let lateral = pivot_inertia - mass * center * center;
+    let axial = 2.0 / 5.0 * bob_mass * 0.01;
+    assert_tensor_eq(
+        properties.inertia_tensor,
+        Matrix::from_diagonal(Vector::new(lateral, axial, lateral)),
+    );
+
+    let body = pendulum.rigid_body(Vector::ZERO, Vector::ZERO).unwrap();
+    assert!((body.mass - mass).abs() < 1e-5);
+}
+
+/// A compound without parts has no mass properties rather than NaNs.
+#[test]
+fn empty_compound_has_no_mass_properties() {
+    assert!(CompoundBody::new().mass_properties().is_none());
+    assert!(CompoundBody::new()
+        .rigid_body(Vector::ZERO, Vector::ZERO)
+        .is_none());
+}
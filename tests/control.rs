@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use pdrust::{
+    clock::SimulationClock,
+    control::{SimulationState, StepSimulation},
+    PDRustPlugin,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(PDRustPlugin);
+    app.update();
+    app
+}
+
+/// Handles requests sent since the last call, as a frame would.
+fn handle_requests(app: &mut App) {
+    app.world.run_schedule(Update);
+}
+
+/// Runs `FixedUpdate` `count` times and returns the number of simulation steps made.
+fn run_fixed_updates(app: &mut App, count: usize) -> u64 {
+    let before = app.world.resource::<SimulationClock>().steps();
+    for _ in 0..count {
+        app.world.run_schedule(FixedUpdate);
+    }
+    app.world.resource::<SimulationClock>().steps() - before
+}
+
+/// A paused simulation does not advance, makes exactly the requested steps and then advances
+/// again when resumed.
+#[test]
+fn pause_step_and_resume() {
+    let mut app = app();
+    assert_eq!(run_fixed_updates(&mut app, 2), 2);
+
+    app.world.resource_mut::<SimulationState>().pause();
+    assert_eq!(run_fixed_updates(&mut app, 5), 0);
+
+    app.world.send_event(StepSimulation(3));
+    handle_requests(&mut app);
+    assert_eq!(run_fixed_updates(&mut app, 5), 3);
+    assert_eq!(
+        *app.world.resource::<SimulationState>(),
+        SimulationState::Paused
+    );
+
+    app.world.resource_mut::<SimulationState>().resume();
+    assert_eq!(run_fixed_updates(&mut app, 4), 4);
+}
+
+/// A request of zero steps neither pauses a running simulation nor steps a paused one.
+#[test]
+fn zero_step_request_is_ignored() {
+    let mut app = app();
+    app.world.send_event(StepSimulation(0));
+    handle_requests(&mut app);
+    assert_eq!(
+        *app.world.resource::<SimulationState>(),
+        SimulationState::Running
+    );
+    assert_eq!(run_fixed_updates(&mut app, 3), 3);
+
+    app.world.resource_mut::<SimulationState>().pause();
+    app.world.send_event(StepSimulation(0));
+    handle_requests(&mut app);
+    assert_eq!(run_fixed_updates(&mut app, 3), 0);
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    world::PhysicsWorld,
+};
+
+/// Velocity and angular velocity of a free damped body decay exponentially, and the lost kinetic
+/// energy is recorded as dissipated.
+#[test]
+fn free_body_slows_down_exponentially() {
+    let (mass, radius, linear_damping, angular_damping) = (2.0, 0.5, 0.4, 0.1);
+    let inertia = 2.0 / 5.0 * mass * radius * radius;
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::RungeKutta4,
+        gravity_vector: Vector::ZERO,
+        ..default()
+    });
+    let body = world.add_body(
+        PhysicsTransform::IDENTITY,
+        RigidBody::new_sphere(
+            mass,
+            radius,
+            Vector::new(mass, 0.0, 0.0),
+            Vector::new(0.0, inertia, 0.0),
+        )
+        .with_damping(linear_damping, angular_damping),
+    );
+    let initial_energy = world.compute_energy();
+
+    let time: Real = 2.0;
+    for _ in 0..120 {
+        world.step(time / 120.0);
+    }
+
+    let transform = world.transform(body);
+    let rigid_body = world.rigid_body(body).unwrap();
+    let velocity = rigid_body.get_velocity().x;
+    let angular_velocity = rigid_body.get_angular_velocity(&transform).y;
+    assert!((velocity - (-linear_damping * time / mass).exp()).abs() < 1e-3);
+    assert!((angular_velocity - (-angular_damping * time / inertia).exp()).abs() < 1e-3);
+
+    let dissipated_energy = world.compute_dissipated_energy();
+    assert!(dissipated_energy > 0.0);
+    let balance = world.compute_energy() + dissipated_energy - initial_energy;
+    assert!(
+        balance.abs() < 1e-2 * initial_energy,
+        "energy balance {balance}"
+    );
+}
+
+/// A falling damped body reaches its terminal velocity, and the potential energy it loses goes
+/// to the dissipated energy.
+#[test]
+fn falling_body_reaches_terminal_velocity() {
+    let (mass, linear_damping) = (1.0, 2.0);
+    let gravity = Vector::new(0.0, -9.81, 0.0);
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::SemiImplicitEuler,
+        gravity_vector: gravity,
+        ..default()
+    });
+    let body = world.add_body(
+        PhysicsTransform::IDENTITY,
+        RigidBody::new_sphere(mass, 0.1, Vector::ZERO, Vector::ZERO)
+            .with_damping(linear_damping, 0.0),
+    );
+    let initial_energy = world.compute_energy();
+
+    for _ in 0..600 {
+        world.step(1.0 / 60.0);
+    }
+
+    let velocity = world.rigid_body(body).unwrap().get_velocity();
+    assert!((velocity - gravity * mass / linear_damping).length() < 1e-3);
+    let balance = world.compute_energy() + world.compute_dissipated_energy() - initial_energy;
+    let fall = -world.transform(body).translation.y * mass * gravity.length();
+    assert!(balance.abs() < 2e-2 * fall, "energy balance {balance}");
+}
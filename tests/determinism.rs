@@ -0,0 +1,111 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use bevy::prelude::*;
+use pdrust::{
+    body::{Body, PhysicsTransform, RigidBody},
+    constraint::distance::DistanceConstraint,
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    PDRustPlugin,
+};
+
+const STEPS: usize = 10_000;
+
+/// Changes the archetype of an entity, and therefore its position in query results.
+#[derive(Component)]
+struct Marker;
+
+/// A chain of boxes hanging on an anchor. Every link is a spring together with a distance
+/// constraint, and every box is also connected to the anchor with a weak spring.
+/// When `shuffle` is set, every other entity of each kind gets a [`Marker`], so queries return
+/// them in a different order, while entities stay the same.
+fn run(shuffle: bool) -> u64 {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(PDRustPlugin);
+    app.insert_resource(SettingsResource {
+        method: IntergrationMethod::SemiImplicitEuler,
+        deterministic: true,
+        ..default()
+    });
+
+    let world = &mut app.world;
+    let anchor = world.spawn((Transform::from_xyz(0.0, 5.0, 0.0), Body)).id();
+    let spring = |first_body, second_body, rest_length, spring_constant| Spring {
+        first_body,
+        first_body_attachment_point_offset: Vector::ZERO,
+        second_body,
+        second_body_attachment_point_offset: Vector::new(0.0, 0.25, 0.0),
+        rest_length,
+        spring_constant,
+        damping_constant: 1.0,
+    };
+    let mut previous = anchor;
+    let mut entities = Vec::new();
+    for i in 0..8 {
+        let body = world
+            .spawn((
+                Transform::from_xyz(i as f32 + 1.0, 5.0, 0.1 * i as f32),
+                Body,
+                RigidBody::new_box(1.0, 1.0, 0.5, 0.5, Vector::ZERO, Vector::new(0.0, 0.1, 0.0)),
+            ))
+            .id();
+        let link = world.spawn(spring(previous, body, 1.0, 200.0)).id();
+        let constraint = world
+            .spawn(DistanceConstraint::new(
+                previous,
+                body,
+                Vector::ZERO,
+                Vector::new(0.0, 0.25, 0.0),
+                0.5,
+                1.0,
+            ))
+            .id();
+        let anchor_link = world
+            .spawn(spring(anchor, body, i as Real + 1.0, 10.0))
+            .id();
+        if shuffle && i % 2 == 0 {
+            entities.extend([body, link, constraint, anchor_link]);
+        }
+        previous = body;
+    }
+    for entity in entities {
+        world.entity_mut(entity).insert(Marker);
+    }
+
+    app.update();
+    for _ in 0..STEPS {
+        app.world.run_schedule(FixedUpdate);
+    }
+
+    let mut hasher = DefaultHasher::new();
+    let mut query = app.world.query::<(Entity, &PhysicsTransform, &RigidBody)>();
+    let mut bodies: Vec<_> = query.iter(&app.world).collect();
+    bodies.sort_by_key(|(entity, _, _)| *entity);
+    for (entity, transform, body) in bodies {
+        entity.hash(&mut hasher);
+        let values = [
+            transform.translation.to_array(),
+            transform.rotation.xyz().to_array(),
+            body.pulse.to_array(),
+            body.angular_momentum.to_array(),
+        ];
+        for value in values.iter().flatten().chain([&transform.rotation.w]) {
+            value.to_bits().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+#[test]
+fn same_scene_gives_same_state() {
+    assert_eq!(run(false), run(false));
+}
+
+#[test]
+fn query_order_does_not_change_state() {
+    assert_eq!(run(false), run(true));
+}
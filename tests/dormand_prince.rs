@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{Body, PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    solver::IntegrationStats,
+    springs::Spring,
+    PDRustPlugin,
+};
+
+/// A bob on a stiff spring released from the horizontal position.
+/// Returns the horizontal position of the bob relative to the spring length and the number of
+/// solver steps for every `FixedUpdate` of the first half swing.
+fn swing(tolerance: Real) -> Vec<(Real, usize)> {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(PDRustPlugin);
+    app.insert_resource(SettingsResource {
+        method: IntergrationMethod::DormandPrince,
+        adaptive_tolerance: tolerance,
+        integration_substeps: 1,
+        ..default()
+    });
+    let anchor = app.world.spawn((Transform::IDENTITY, Body)).id();
+    let bob = app
+        .world
+        .spawn((
+            Transform::from_xyz(1.0, 0.0, 0.0),
+            Body,
+            RigidBody::new_sphere(1.0, 0.1, Vector::ZERO, Vector::ZERO),
+        ))
+        .id();
+    app.world.spawn(Spring {
+        first_body: bob,
+        second_body: anchor,
+        spring_constant: 3000.0,
+        ..default()
+    });
+
+    app.update();
+    let mut swing = Vec::new();
+    loop {
+        app.world.run_schedule(FixedUpdate);
+        let x = app
+            .world
+            .get::<PhysicsTransform>(bob)
+            .unwrap()
+            .translation
+            .x;
+        swing.push((x, app.world.resource::<IntegrationStats>().steps));
+        if x < -0.9 {
+            return swing;
+        }
+    }
+}
+
+/// Motion is fastest at the bottom of the swing, so the solver makes more steps there than near
+/// the turning point.
+#[test]
+fn steps_are_refined_at_bottom_of_swing() {
+    let swing = swing(1e-6);
+    let max_steps = |near: fn(Real) -> bool| {
+        swing
+            .iter()
+            .filter(|(x, _)| near(*x))
+            .map(|(_, steps)| *steps)
+            .max()
+            .unwrap()
+    };
+    let turning_point = max_steps(|x| x > 0.9);
+    let bottom = max_steps(|x| x.abs() < 0.2);
+    assert!(
+        bottom > turning_point,
+        "{bottom} steps at the bottom, {turning_point} at the turning point"
+    );
+}
+
+/// A tighter tolerance takes more steps.
+#[test]
+fn steps_grow_as_tolerance_is_tightened() {
+    let total_steps = |tolerance| {
+        swing(tolerance)
+            .iter()
+            .take(30)
+            .map(|(_, steps)| steps)
+            .sum::<usize>()
+    };
+    let loose = total_steps(1e-4);
+    let tight = total_steps(1e-6);
+    assert!(
+        tight > loose,
+        "{tight} steps with a tight tolerance, {loose} with a loose one"
+    );
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{drag::Drag, PhysicsTransform, RigidBody},
+    math::{consts::PI, Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    wind::Wind,
+    world::PhysicsWorld,
+};
+
+/// A falling sphere reaches the velocity at which drag balances gravity, and the lost energy is
+/// recorded as dissipated.
+#[test]
+fn sphere_reaches_terminal_velocity() {
+    let (mass, radius, coefficient) = (0.5, 0.2, 0.47);
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::RungeKutta4,
+        ..default()
+    });
+    let body = world.add_body(
+        PhysicsTransform::IDENTITY,
+        RigidBody::new_sphere(mass, radius, Vector::ZERO, Vector::ZERO)
+            .with_drag(Drag::new_sphere(coefficient, radius)),
+    );
+    let initial_energy = world.compute_energy();
+
+    for _ in 0..1200 {
+        world.step(1.0 / 60.0);
+    }
+
+    let area = PI * radius * radius;
+    let terminal_velocity = (2.0 * mass * 9.81 / (1.225 * coefficient * area)).sqrt();
+    let velocity = world.rigid_body(body).unwrap().get_velocity();
+    assert!(
+        (velocity.y + terminal_velocity).abs() < 1e-2 * terminal_velocity,
+        "velocity {velocity}, expected {terminal_velocity}"
+    );
+    let balance = world.compute_energy() + world.compute_dissipated_energy() - initial_energy;
+    let fall = -world.transform(body).translation.y * mass * 9.81;
+    assert!(balance.abs() < 1e-2 * fall, "energy balance {balance}");
+}
+
+/// A pendulum sleeping in still air is woken up by a steady wind and comes to rest at the angle
+/// where drag and gravity balance, without falling asleep again.
+#[test]
+fn pendulum_leans_in_wind() {
+    let (mass, length, coefficient, wind_speed) = (0.1, 1.0, 1.05, 4.0);
+    let size: Real = 0.3;
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::SemiImplicitEuler,
+        allow_sleeping: true,
+        ..default()
+    });
+    let pivot = world.add_static_body(PhysicsTransform::IDENTITY);
+    let bob = world.add_body(
+        PhysicsTransform::from_xyz(0.0, -length, 0.0),
+        RigidBody::new_box(mass, size, size, size, Vector::ZERO, Vector::ZERO)
+            .with_drag(Drag::new_box(coefficient, size, size, size)),
+    );
+    world.add_distance_constraint(pivot, bob, Vector::ZERO, Vector::ZERO, length, length);
+    let is_sleeping = |world: &PhysicsWorld| world.rigid_body(bob).unwrap().sleeping;
+
+    for _ in 0..120 {
+        world.step(1.0 / 60.0);
+    }
+    assert!(is_sleeping(&world));
+
+    world.set_wind(Wind::uniform(Vector::new(wind_speed, 0.0, 0.0)));
+    for _ in 0..1800 {
+        world.step(1.0 / 60.0);
+        assert!(!is_sleeping(&world));
+    }
+
+    // The bob hangs still, so the wind blows onto its face
+    let drag = 0.5 * 1.225 * coefficient * size * size * wind_speed * wind_speed;
+    let expected_angle = (drag / (mass * 9.81)).atan();
+    let position = world.transform(bob).translation;
+    let angle = position.x.atan2(-position.y);
+    assert!(
+        (angle - expected_angle).abs() < 2e-2,
+        "angle {angle}, expected {expected_angle}"
+    );
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{Body, PhysicsTransform, RigidBody},
+    control::SimulationState,
+    energy::EnergyProjection,
+    history::{ScrubHistory, SimulationHistory},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    world::{BodyHandle, PhysicsWorld},
+    PDRustPlugin,
+};
+
+const DT: Real = 1.0 / 64.0;
+
+fn spring() -> Spring {
+    Spring {
+        spring_constant: 50.0,
+        ..default()
+    }
+}
+
+/// An oscillator integrated with explicit Euler method, whose energy would drift without the
+/// projection. Returns the world, the anchor and the bob.
+fn oscillator(anchor_velocity: Vector) -> (PhysicsWorld, BodyHandle, BodyHandle) {
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::EulerMethod,
+        energy_projection: true,
+        ..default()
+    });
+    let anchor =
+        world.add_kinematic_body(PhysicsTransform::IDENTITY, anchor_velocity, Vector::ZERO);
+    let bob = world.add_body(
+        PhysicsTransform::from_xyz(0.0, -2.0, 0.0),
+        RigidBody::new_sphere(1.0, 0.1, Vector::new(1.0, 0.0, 0.0), Vector::ZERO),
+    );
+    world.add_spring(bob, anchor, spring());
+    (world, anchor, bob)
+}
+
+/// Work of an external force or of a moving kinematic body is not projected away, and the
+/// energy the simulation ends up with is the one kept afterwards.
+#[test]
+fn external_work_is_not_projected_away() {
+    let (mut world, _, bob) = oscillator(Vector::ZERO);
+    world.step(DT);
+    assert!(world.energy_projection().correction().is_some());
+
+    world.set_external_force(bob, Vector::new(5.0, 0.0, 0.0), Vector::ZERO);
+    let energy = world.compute_energy();
+    for _ in 0..32 {
+        world.step(DT);
+        assert_eq!(world.energy_projection().correction(), None);
+    }
+    assert!(world.compute_energy() > energy + 1.0);
+
+    world.set_external_force(bob, Vector::ZERO, Vector::ZERO);
+    let energy = world.compute_energy();
+    world.step(DT);
+    assert!(world.energy_projection().correction().is_some());
+    assert_eq!(world.energy_projection().initial_energy(), Some(energy));
+
+    let (mut world, anchor, _) = oscillator(Vector::new(2.0, 0.0, 0.0));
+    for _ in 0..32 {
+        world.step(DT);
+        assert_eq!(world.energy_projection().correction(), None);
+    }
+    world.set_kinematic_velocity(anchor, Vector::ZERO, Vector::ZERO);
+    let energy = world.compute_energy();
+    world.step(DT);
+    assert!(world.energy_projection().correction().is_some());
+    assert_eq!(world.energy_projection().initial_energy(), Some(energy));
+}
+
+/// The initial energy is taken again when bodies are added to a world, or the history of an app
+/// is restored or its bodies change.
+#[test]
+fn initial_energy_is_taken_again_when_simulation_changes() {
+    let (mut world, anchor, _) = oscillator(Vector::ZERO);
+    world.step(DT);
+    let bob = world.add_body(
+        PhysicsTransform::from_xyz(0.0, 2.0, 0.0),
+        RigidBody::new_sphere(1.0, 0.1, Vector::ZERO, Vector::ZERO),
+    );
+    world.add_spring(bob, anchor, spring());
+    assert_eq!(world.energy_projection().initial_energy(), None);
+    let energy = world.compute_energy();
+    world.step(DT);
+    assert_eq!(world.energy_projection().initial_energy(), Some(energy));
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(PDRustPlugin);
+    app.insert_resource(SimulationHistory::with_capacity(64));
+    app.insert_resource(SettingsResource {
+        method: IntergrationMethod::EulerMethod,
+        energy_projection: true,
+        ..default()
+    });
+    let anchor = app.world.spawn((Transform::IDENTITY, Body)).id();
+    let bob = app
+        .world
+        .spawn((
+            Transform::from_xyz(0.0, -2.0, 0.0),
+            Body,
+            RigidBody::new_sphere(1.0, 0.1, Vector::new(1.0, 0.0, 0.0), Vector::ZERO),
+        ))
+        .id();
+    app.world.spawn(Spring {
+        first_body: bob,
+        second_body: anchor,
+        ..spring()
+    });
+    app.update();
+    let initial_energy = |app: &App| app.world.resource::<EnergyProjection>().initial_energy();
+
+    for _ in 0..16 {
+        app.world.run_schedule(FixedUpdate);
+    }
+    assert!(initial_energy(&app).is_some());
+    app.world.send_event(ScrubHistory(-8));
+    app.world.run_schedule(Update);
+    assert_eq!(initial_energy(&app), None);
+
+    app.world.resource_mut::<SimulationState>().resume();
+    app.world.run_schedule(FixedUpdate);
+    assert!(initial_energy(&app).is_some());
+    app.world.spawn((
+        Transform::from_xyz(1.0, 0.0, 0.0),
+        Body,
+        RigidBody::new_sphere(1.0, 0.1, Vector::ZERO, Vector::ZERO),
+    ));
+    app.world.run_schedule(Update);
+    assert_eq!(initial_energy(&app), None);
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    world::PhysicsWorld,
+};
+
+/// Results of spinning a box around its intermediate axis for a minute.
+struct Spin {
+    /// Whether the intermediate axis turned upside down at least once
+    flipped: bool,
+    /// Largest relative change of kinetic energy
+    energy_drift: Real,
+    /// Largest relative change of angular momentum
+    angular_momentum_drift: Real,
+}
+
+fn spin(implicit_gyroscopic_torque: bool) -> Spin {
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::SemiImplicitEuler,
+        implicit_gyroscopic_torque,
+        integration_substeps: 1,
+        gravity_vector: Vector::ZERO,
+        ..default()
+    });
+    // Intermediate axis is Y, a tiny spin around X makes the rotation unstable
+    let body = world.add_body(
+        PhysicsTransform::IDENTITY,
+        RigidBody::new_box(
+            1.0,
+            3.0,
+            1.5,
+            0.5,
+            Vector::ZERO,
+            Vector::new(0.001, 40.0, 0.0),
+        ),
+    );
+    let energy = world.compute_energy();
+    let angular_momentum = world.rigid_body(body).unwrap().angular_momentum;
+
+    let mut result = Spin {
+        flipped: false,
+        energy_drift: 0.0,
+        angular_momentum_drift: 0.0,
+    };
+    for _ in 0..64 * 60 {
+        world.step(1.0 / 64.0);
+        let axis = world.transform(body).rotation * Vector::Y;
+        result.flipped |= axis.y < -0.9;
+        result.energy_drift = result
+            .energy_drift
+            .max((world.compute_energy() - energy).abs() / energy);
+        let drift = (world.rigid_body(body).unwrap().angular_momentum - angular_momentum).length();
+        result.angular_momentum_drift = result
+            .angular_momentum_drift
+            .max(drift / angular_momentum.length());
+    }
+    result
+}
+
+/// A box spinning fast around its intermediate axis keeps flipping over (Dzhanibekov effect).
+/// The implicit gyroscopic term keeps its energy and angular momentum, while the explicit
+/// rotation lets the box drift towards spinning around another axis.
+#[test]
+fn intermediate_axis_flips_with_bounded_energy() {
+    let implicit = spin(true);
+    assert!(implicit.flipped);
+    assert!(
+        implicit.energy_drift < 1e-2,
+        "energy drifted by {}",
+        implicit.energy_drift
+    );
+    assert!(
+        implicit.angular_momentum_drift < 1e-3,
+        "angular momentum drifted by {}",
+        implicit.angular_momentum_drift
+    );
+
+    let explicit = spin(false);
+    assert!(
+        explicit.energy_drift > 100.0 * implicit.energy_drift,
+        "explicit energy drift {} is not worse than implicit {}",
+        explicit.energy_drift,
+        implicit.energy_drift
+    );
+}
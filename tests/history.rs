@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{Body, PhysicsTransform, RigidBody},
+    clock::SimulationClock,
+    constraint::distance::DistanceConstraint,
+    control::SimulationState,
+    history::{ScrubHistory, SimulationHistory},
+    math::Vector,
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    PDRustPlugin,
+};
+
+const STEPS: usize = 100;
+
+/// A box hanging on a spring next to a box hanging on a distance constraint.
+fn scene() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(PDRustPlugin);
+    app.insert_resource(SimulationHistory::with_capacity(3 * STEPS));
+    app.insert_resource(SettingsResource {
+        method: IntergrationMethod::SemiImplicitEuler,
+        deterministic: true,
+        ..default()
+    });
+
+    let world = &mut app.world;
+    let anchor = world.spawn((Transform::IDENTITY, Body)).id();
+    let body = |x| {
+        (
+            Transform::from_xyz(x, -1.0, 0.0),
+            Body,
+            RigidBody::new_box(1.0, 0.5, 0.5, 0.5, Vector::ZERO, Vector::ZERO),
+        )
+    };
+    let spring_body = world.spawn(body(1.0)).id();
+    let constrained_body = world.spawn(body(-1.0)).id();
+    world.spawn(Spring {
+        first_body: anchor,
+        first_body_attachment_point_offset: Vector::ZERO,
+        second_body: spring_body,
+        second_body_attachment_point_offset: Vector::new(0.0, 0.25, 0.0),
+        rest_length: 1.0,
+        spring_constant: 50.0,
+        damping_constant: 0.0,
+    });
+    world.spawn(DistanceConstraint::new(
+        anchor,
+        constrained_body,
+        Vector::ZERO,
+        Vector::new(0.0, 0.25, 0.0),
+        0.0,
+        1.0,
+    ));
+
+    app.update();
+    app
+}
+
+fn run_steps(app: &mut App, steps: usize) {
+    for _ in 0..steps {
+        app.world.run_schedule(FixedUpdate);
+    }
+}
+
+fn bodies(app: &mut App) -> Vec<(Entity, PhysicsTransform, Vector, Vector)> {
+    let mut query = app.world.query::<(Entity, &PhysicsTransform, &RigidBody)>();
+    let mut bodies: Vec<_> = query
+        .iter(&app.world)
+        .map(|(e, t, b)| (e, *t, b.pulse, b.angular_momentum))
+        .collect();
+    bodies.sort_by_key(|(entity, _, _, _)| *entity);
+    bodies
+}
+
+fn steps(app: &App) -> u64 {
+    app.world.resource::<SimulationClock>().steps()
+}
+
+#[test]
+fn resuming_from_past_state_repeats_trajectory() {
+    let mut app = scene();
+    run_steps(&mut app, STEPS);
+    let middle = bodies(&mut app);
+    run_steps(&mut app, STEPS);
+    let end = bodies(&mut app);
+
+    app.world.send_event(ScrubHistory(-(STEPS as isize)));
+    app.world.run_schedule(Update);
+    assert_eq!(
+        *app.world.resource::<SimulationState>(),
+        SimulationState::Paused
+    );
+    assert_eq!(steps(&app), STEPS as u64);
+    assert_eq!(bodies(&mut app), middle);
+
+    app.world.resource_mut::<SimulationState>().resume();
+    run_steps(&mut app, STEPS);
+    assert_eq!(steps(&app), 2 * STEPS as u64);
+    assert_eq!(bodies(&mut app), end);
+    assert_eq!(app.world.resource::<SimulationHistory>().position(), None);
+}
+
+#[test]
+fn rewinding_returns_to_initial_state() {
+    let mut app = scene();
+    let initial = bodies(&mut app);
+    run_steps(&mut app, STEPS);
+
+    app.world.resource_mut::<SimulationState>().rewind();
+    run_steps(&mut app, 2 * STEPS);
+    assert_eq!(
+        *app.world.resource::<SimulationState>(),
+        SimulationState::Paused
+    );
+    assert_eq!(steps(&app), 0);
+    assert_eq!(bodies(&mut app), initial);
+
+    app.world.send_event(ScrubHistory(STEPS as isize));
+    app.world.run_schedule(Update);
+    assert_eq!(steps(&app), STEPS as u64);
+}
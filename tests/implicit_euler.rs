@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    world::PhysicsWorld,
+};
+
+/// Default `FixedUpdate` timestep of Bevy
+const DT: Real = 1.0 / 64.0;
+
+/// A net of light balls connected with stiff, heavily damped springs, hanging on two anchors,
+/// with its middle ball kicked.
+/// Returns the largest distance of a ball from its initial position after two seconds, or
+/// `None` if the simulation blew up.
+fn simulate_net(method: IntergrationMethod) -> Option<Real> {
+    const SIZE: usize = 5;
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method,
+        ..default()
+    });
+    let spring = Spring {
+        spring_constant: 5e4,
+        damping_constant: 200.0,
+        ..default()
+    };
+    let mut balls = Vec::new();
+    for i in 0..SIZE {
+        for j in 0..SIZE {
+            let transform = PhysicsTransform::from_xyz(i as Real, -(j as Real), 0.0);
+            // The middle ball is kicked out of the plane of the net
+            let pulse = if i == SIZE / 2 && j == SIZE / 2 {
+                Vector::new(0.0, 0.0, 0.5)
+            } else {
+                Vector::ZERO
+            };
+            balls.push(world.add_body(
+                transform,
+                RigidBody::new_sphere(0.05, 0.1, pulse, Vector::ZERO),
+            ));
+        }
+    }
+    for i in 0..SIZE {
+        for j in 0..SIZE {
+            let ball = balls[i * SIZE + j];
+            if i + 1 < SIZE {
+                world.add_spring(ball, balls[(i + 1) * SIZE + j], spring);
+            }
+            if j + 1 < SIZE {
+                world.add_spring(ball, balls[i * SIZE + j + 1], spring);
+            }
+        }
+    }
+    for (x, ball) in [(-1.0, balls[0]), (SIZE as Real, balls[(SIZE - 1) * SIZE])] {
+        let anchor = world.add_static_body(PhysicsTransform::from_xyz(x, 0.0, 0.0));
+        world.add_spring(ball, anchor, spring);
+    }
+    let initial: Vec<Vector> = balls
+        .iter()
+        .map(|ball| world.transform(*ball).translation)
+        .collect();
+
+    for _ in 0..128 {
+        world.step(DT);
+    }
+
+    let displacements: Vec<Real> = balls
+        .iter()
+        .zip(initial)
+        .map(|(ball, initial)| (world.transform(*ball).translation - initial).length())
+        .collect();
+    if displacements.iter().any(|d| !d.is_finite()) {
+        return None;
+    }
+    displacements.into_iter().reduce(Real::max)
+}
+
+/// Springs are so stiff and damped that the explicit method is unstable with the default
+/// timestep and substeps, while the implicit one keeps the net hanging.
+#[test]
+fn stiff_damped_net_stays_bounded() {
+    let explicit = simulate_net(IntergrationMethod::EulerMethod);
+    assert!(
+        explicit.map_or(true, |displacement| displacement > 1e3),
+        "explicit Euler stayed bounded: {explicit:?}"
+    );
+
+    let implicit = simulate_net(IntergrationMethod::ImplicitEuler).expect("implicit Euler blew up");
+    assert!(implicit < 1.0, "net moved by {implicit}");
+}
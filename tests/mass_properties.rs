@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{
+        mass_properties::MassProperties,
+        shape::{Cone, Ellipsoid},
+        RigidBody,
+    },
+    math::{to_f64, Matrix, Real, Vector},
+};
+
+/// Largest difference between elements of tensors relative to the largest element of `expected`.
+fn relative_error(actual: Matrix, expected: Matrix) -> Real {
+    let difference = (actual - expected).to_cols_array();
+    let expected = expected.to_cols_array();
+    let max = |m: [Real; 9]| m.iter().fold(0.0 as Real, |acc, x| acc.max(x.abs()));
+    max(difference) / max(expected)
+}
+
+/// A box away from the mesh origin has its exact mass, center and inertia tensor.
+#[test]
+fn box_mesh_matches_analytic_box() {
+    let density = 2.0;
+    let mesh = Mesh::from(shape::Box {
+        min_x: 1.0,
+        max_x: 2.0,
+        min_y: -1.0,
+        max_y: 1.0,
+        min_z: 0.0,
+        max_z: 3.0,
+    });
+
+    let properties = MassProperties::from_mesh(&mesh, density).unwrap();
+
+    let mass = density * 6.0;
+    let expected = RigidBody::new_box(mass, 1.0, 2.0, 3.0, Vector::ZERO, Vector::ZERO);
+    assert!(
+        (properties.mass - mass).abs() < 1e-4,
+        "mass {}",
+        properties.mass
+    );
+    assert!(
+        (properties.center_of_mass - Vector::new(1.5, 0.0, 1.5)).length() < 1e-5,
+        "center of mass {}",
+        properties.center_of_mass
+    );
+    let error = relative_error(properties.inertia_tensor, expected.intertia_tensor_body);
+    assert!(error < 1e-4, "inertia tensor {}", properties.inertia_tensor);
+}
+
+/// Tessellated curved shapes approach analytic values of the shapes they approximate.
+#[test]
+fn curved_meshes_approach_analytic_shapes() {
+    let mesh = Mesh::from(Cone {
+        radius: 1.0,
+        height: 2.0,
+        resolution: 128,
+    });
+    let properties = MassProperties::from_mesh(&mesh, 1.0).unwrap();
+    let cone = RigidBody::new_cone(properties.mass, 1.0, 2.0, Vector::ZERO, Vector::ZERO);
+    let volume = std::f64::consts::PI * 2.0 / 3.0;
+    assert!((to_f64(properties.mass) - volume).abs() / volume < 1e-2);
+    // The cone mesh is centered at its center of mass
+    assert!(properties.center_of_mass.length() < 1e-4);
+    assert!(relative_error(properties.inertia_tensor, cone.intertia_tensor_body) < 1e-2);
+
+    let mesh = Mesh::from(Ellipsoid {
+        a: 1.0,
+        b: 2.0,
+        c: 0.5,
+        sectors: 128,
+        stacks: 64,
+    });
+    let properties = MassProperties::from_mesh(&mesh, 1.0).unwrap();
+    let ellipsoid =
+        RigidBody::new_ellipsoid(properties.mass, 1.0, 2.0, 0.5, Vector::ZERO, Vector::ZERO);
+    assert!(properties.center_of_mass.length() < 1e-4);
+    assert!(relative_error(properties.inertia_tensor, ellipsoid.intertia_tensor_body) < 1e-2);
+}
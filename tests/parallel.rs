@@ -0,0 +1,98 @@
+use bevy::{
+    prelude::*,
+    tasks::{ComputeTaskPool, TaskPoolBuilder},
+};
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    world::PhysicsWorld,
+};
+
+const SIZE: usize = 20;
+
+/// A cloth of bodies connected with springs, large enough to be split between threads.
+/// Returns bits of transforms and momenta of all bodies after a second.
+fn simulate_cloth(method: IntergrationMethod, parallel: bool) -> Vec<u64> {
+    // Split work between several threads even on a single core machine
+    ComputeTaskPool::get_or_init(|| TaskPoolBuilder::new().num_threads(4).build());
+
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method,
+        parallel,
+        ..default()
+    });
+    let mut bodies = Vec::new();
+    for i in 0..SIZE {
+        for j in 0..SIZE {
+            let transform = PhysicsTransform::from_xyz(i as Real, 0.0, j as Real);
+            bodies.push(if j == 0 {
+                world.add_static_body(transform)
+            } else {
+                world.add_body(
+                    transform,
+                    RigidBody::new_box(1.0, 0.2, 0.2, 0.2, Vector::ZERO, Vector::ZERO),
+                )
+            });
+        }
+    }
+    let spring = Spring {
+        first_body_attachment_point_offset: Vector::new(0.1, 0.0, 0.0),
+        spring_constant: 100.0,
+        damping_constant: 1.0,
+        ..default()
+    };
+    for i in 0..SIZE {
+        for j in 0..SIZE {
+            let body = bodies[i * SIZE + j];
+            if i + 1 < SIZE {
+                world.add_spring(body, bodies[(i + 1) * SIZE + j], spring);
+            }
+            if j + 1 < SIZE {
+                world.add_spring(body, bodies[i * SIZE + j + 1], spring);
+            }
+        }
+    }
+
+    for _ in 0..64 {
+        world.step(1.0 / 64.0);
+    }
+
+    bodies
+        .iter()
+        .flat_map(|body| {
+            let transform = world.transform(*body);
+            let (pulse, angular_momentum) = world
+                .rigid_body(*body)
+                .map(|b| (b.pulse, b.angular_momentum))
+                .unwrap_or_default();
+            [
+                transform.translation.to_array(),
+                transform.rotation.xyz().to_array(),
+                pulse.to_array(),
+                angular_momentum.to_array(),
+            ]
+            .into_iter()
+            .flatten()
+            .chain([transform.rotation.w])
+            .map(|value| value.to_bits() as u64)
+        })
+        .collect()
+}
+
+#[test]
+fn parallel_runge_kutta_is_bit_identical() {
+    assert_eq!(
+        simulate_cloth(IntergrationMethod::RungeKutta4, false),
+        simulate_cloth(IntergrationMethod::RungeKutta4, true)
+    );
+}
+
+#[test]
+fn parallel_semi_implicit_euler_is_bit_identical() {
+    assert_eq!(
+        simulate_cloth(IntergrationMethod::SemiImplicitEuler, false),
+        simulate_cloth(IntergrationMethod::SemiImplicitEuler, true)
+    );
+}
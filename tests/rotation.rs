@@ -0,0 +1,88 @@
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Matrix, Quaternion, Real, Vector},
+    solver::{
+        state::{BodyState, PhysicsState},
+        EulerSolver, RungeKuttaSolver, SemiImplicitEulerSolver, SimulationSolver, VerletSolver,
+    },
+};
+
+const DT: Real = 1.0 / 600.0;
+const STEPS: usize = 10_000;
+
+fn solvers() -> Vec<(&'static str, Box<dyn SimulationSolver>)> {
+    vec![
+        ("euler", Box::new(EulerSolver {})),
+        ("semi-implicit euler", Box::new(SemiImplicitEulerSolver {})),
+        ("verlet", Box::new(VerletSolver {})),
+        ("runge-kutta", Box::new(RungeKuttaSolver {})),
+    ]
+}
+
+/// A single torque-free body in zero gravity.
+fn free_body(body: RigidBody) -> PhysicsState {
+    PhysicsState::new(
+        vec![BodyState::new(PhysicsTransform::IDENTITY, Some(body))],
+        vec![],
+        Vector::ZERO,
+    )
+}
+
+/// Angular velocity in **Body** coordinates.
+fn body_angular_velocity(state: &PhysicsState) -> Vector {
+    let body_state = &state.bodies[0];
+    let omega = body_state
+        .rigid_body
+        .unwrap()
+        .get_angular_velocity(&body_state.transform);
+    body_state.transform.rotation.inverse() * omega
+}
+
+#[test]
+fn torque_free_symmetric_top_keeps_spin_rate() {
+    // Symmetric about Z axis, angular momentum is slightly tilted from it, so the top precesses.
+    let inertia = Matrix::from_diagonal(Vector::new(1.0, 1.0, 2.0));
+    let body = RigidBody::new(1.0, inertia, Vector::ZERO, Vector::new(1.0, 0.0, 20.0));
+
+    for (name, solver) in solvers() {
+        let mut state = free_body(body);
+        let initial = body_angular_velocity(&state);
+        solver.integrate(&mut state, DT * STEPS as Real, STEPS);
+        let result = body_angular_velocity(&state);
+
+        assert!(
+            (result.z - initial.z).abs() < 1e-3 * initial.z,
+            "{name}: spin rate changed from {} to {}",
+            initial.z,
+            result.z
+        );
+        assert!(
+            (result.length() - initial.length()).abs() < 1e-3 * initial.length(),
+            "{name}: angular speed changed from {} to {}",
+            initial.length(),
+            result.length()
+        );
+    }
+}
+
+#[test]
+fn fast_spin_is_not_distorted() {
+    let spin_rate = 50.0;
+    let body = RigidBody::new_sphere(1.0, 1.0, Vector::ZERO, Vector::ZERO);
+    let inertia = body.intertia_tensor_body.z_axis.z;
+    let body = RigidBody {
+        angular_momentum: Vector::new(0.0, 0.0, spin_rate * inertia),
+        ..body
+    };
+
+    for (name, solver) in solvers() {
+        let mut state = free_body(body);
+        let steps = 1000;
+        let time = DT * 8.0 * steps as Real;
+        solver.integrate(&mut state, time, steps);
+
+        let expected = Quaternion::from_rotation_z(spin_rate * time);
+        let angle = state.bodies[0].transform.rotation.angle_between(expected);
+        assert!(angle < 1e-2, "{name}: orientation is off by {angle} rad");
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use bevy::prelude::*;
+use common::assert_tensor_eq;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Matrix, Real, Vector},
+    settings::SettingsResource,
+    world::PhysicsWorld,
+};
+
+/// Inertia tensors match textbook formulas, and shapes degenerate into each other consistently.
+#[test]
+fn inertia_tensors_match_analytic_values() {
+    let mass = 3.0;
+    let body = RigidBody::new_box(mass, 1.0, 2.0, 3.0, Vector::ZERO, Vector::ZERO);
+    let expected = Matrix::from_diagonal(Vector::new(13.0, 10.0, 5.0) * mass / 12.0);
+    assert_tensor_eq(body.intertia_tensor_body, expected);
+    assert_tensor_eq(body.intertia_tensor_body_inv, expected.inverse());
+
+    let sphere = RigidBody::new_sphere(mass, 0.5, Vector::ZERO, Vector::ZERO);
+    let ellipsoid = RigidBody::new_ellipsoid(mass, 0.5, 0.5, 0.5, Vector::ZERO, Vector::ZERO);
+    let capsule = RigidBody::new_capsule(mass, 0.5, 0.0, Vector::ZERO, Vector::ZERO);
+    assert_tensor_eq(ellipsoid.intertia_tensor_body, sphere.intertia_tensor_body);
+    assert_tensor_eq(capsule.intertia_tensor_body, sphere.intertia_tensor_body);
+
+    let hollow_sphere = RigidBody::new_hollow_sphere(mass, 0.5, Vector::ZERO, Vector::ZERO);
+    assert_tensor_eq(
+        hollow_sphere.intertia_tensor_body,
+        Matrix::IDENTITY * (mass * 0.25 * 2.0 / 3.0),
+    );
+
+    // A long thin cylinder is a rod
+    let length: Real = 4.0;
+    let cylinder = RigidBody::new_cylinder(mass, 1e-3, length, Vector::ZERO, Vector::ZERO);
+    let rod = RigidBody::new_rod(mass, length, Vector::ZERO, Vector::ZERO);
+    assert_tensor_eq(cylinder.intertia_tensor_body, rod.intertia_tensor_body);
+
+    // A long capsule is mostly a cylinder
+    let capsule = RigidBody::new_capsule(mass, 0.1, 100.0, Vector::ZERO, Vector::ZERO);
+    let cylinder = RigidBody::new_cylinder(mass, 0.1, 100.0, Vector::ZERO, Vector::ZERO);
+    let ratio = capsule.intertia_tensor_body.x_axis.x / cylinder.intertia_tensor_body.x_axis.x;
+    assert!((ratio - 1.0).abs() < 1e-2, "ratio {ratio}");
+
+    let cone = RigidBody::new_cone(mass, 1.0, 4.0, Vector::ZERO, Vector::ZERO);
+    let expected = Matrix::from_diagonal(Vector::new(
+        mass * (3.0 / 20.0 + 3.0 * 16.0 / 80.0),
+        mass * 3.0 / 10.0,
+        mass * (3.0 / 20.0 + 3.0 * 16.0 / 80.0),
+    ));
+    assert_tensor_eq(cone.intertia_tensor_body, expected);
+}
+
+/// A tumbling rod keeps spinning with a constant angular velocity and never spins around its axis.
+#[test]
+fn rod_tumbles_around_lateral_axis() {
+    for implicit_gyroscopic_torque in [false, true] {
+        let mut world = PhysicsWorld::new(SettingsResource {
+            gravity_vector: Vector::ZERO,
+            implicit_gyroscopic_torque,
+            ..default()
+        });
+        let angular_momentum = Vector::new(1.0, 0.0, 0.0);
+        let rod = world.add_body(
+            PhysicsTransform::IDENTITY,
+            RigidBody::new_rod(2.0, 3.0, Vector::ZERO, angular_momentum),
+        );
+        for _ in 0..600 {
+            world.step(1.0 / 60.0);
+        }
+
+        let transform = world.transform(rod);
+        let body = world.rigid_body(rod).unwrap();
+        let omega = body.get_angular_velocity(&transform);
+        assert!(transform.rotation.is_finite());
+        assert!(
+            (omega - Vector::new(2.0 / 3.0, 0.0, 0.0)).length() < 1e-3,
+            "omega {omega}"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::SettingsResource,
+    springs::Spring,
+    world::{BodyHandle, PhysicsWorld},
+};
+
+const DT: Real = 1.0 / 64.0;
+
+fn world() -> PhysicsWorld {
+    PhysicsWorld::new(SettingsResource {
+        allow_sleeping: true,
+        ..default()
+    })
+}
+
+fn is_sleeping(world: &PhysicsWorld, body: BodyHandle) -> bool {
+    world.rigid_body(body).unwrap().sleeping
+}
+
+/// Two damped boxes hanging in a chain from `pivot`. Returns the boxes after they fell asleep.
+fn sleeping_chain(world: &mut PhysicsWorld, pivot: BodyHandle) -> [BodyHandle; 2] {
+    let chain = [-1.0, -2.0].map(|y| {
+        world.add_body(
+            PhysicsTransform::from_xyz(0.5, y, 0.0),
+            RigidBody::new_box(1.0, 0.2, 0.2, 0.2, Vector::ZERO, Vector::ZERO)
+                .with_damping(2.0, 0.5),
+        )
+    });
+    world.add_distance_constraint(pivot, chain[0], Vector::ZERO, Vector::ZERO, 1.0, 1.0);
+    world.add_distance_constraint(chain[0], chain[1], Vector::ZERO, Vector::ZERO, 1.0, 1.0);
+    for _ in 0..64 * 20 {
+        world.step(DT);
+        if chain.iter().all(|body| is_sleeping(world, *body)) {
+            return chain;
+        }
+    }
+    panic!("the chain did not fall asleep");
+}
+
+/// Bodies of an island fall asleep together once all of them have been resting for
+/// `time_to_sleep`.
+#[test]
+fn island_falls_asleep_after_time_to_sleep() {
+    let mut world = world();
+    world.settings.gravity_vector = Vector::ZERO;
+    let first = world.add_body(
+        PhysicsTransform::IDENTITY,
+        RigidBody::new_sphere(1.0, 0.1, Vector::ZERO, Vector::ZERO),
+    );
+    // The second body is slowly moving at first and then stopped
+    let second = world.add_body(
+        PhysicsTransform::from_xyz(1.0, 0.0, 0.0),
+        RigidBody::new_sphere(1.0, 0.1, Vector::new(0.0, 0.0, 0.1), Vector::ZERO),
+    );
+    world.add_spring(first, second, Spring::default());
+
+    for _ in 0..32 {
+        world.step(DT);
+    }
+    world.body_mut(second).rigid_body.as_mut().unwrap().pulse = Vector::ZERO;
+    world.body_mut(first).rigid_body.as_mut().unwrap().pulse = Vector::ZERO;
+    for _ in 0..60 {
+        world.step(DT);
+    }
+    // The first body has been resting for longer than a second, but the island is awake
+    assert!(!is_sleeping(&world, first));
+    assert!(!is_sleeping(&world, second));
+
+    for _ in 0..10 {
+        world.step(DT);
+    }
+    assert!(is_sleeping(&world, first));
+    assert!(is_sleeping(&world, second));
+}
+
+/// The bridge of `fixed_length_bridge` example: boxes connected with distance constraints
+/// between two anchors. Once damping has settled it, the whole bridge falls asleep.
+#[test]
+fn settled_bridge_falls_asleep() {
+    let mut world = world();
+    let (n, cube_size, link) = (50, 0.2, 1.0);
+    let start = Vector::new(-5.0, 0.0, 0.0);
+    let mut previous = world.add_static_body(PhysicsTransform::from_translation(start));
+    let mut boxes = Vec::new();
+    for i in 0..=n {
+        let position = start + Vector::X * (link * (i as Real + 1.0) + cube_size * i as Real);
+        let body = if i < n {
+            world.add_body(
+                PhysicsTransform::from_translation(position),
+                RigidBody::new_box(
+                    1.0,
+                    cube_size,
+                    cube_size,
+                    cube_size,
+                    Vector::ZERO,
+                    Vector::ZERO,
+                )
+                .with_damping(1.0, 0.1),
+            )
+        } else {
+            world.add_static_body(PhysicsTransform::from_translation(position))
+        };
+        world.add_distance_constraint(previous, body, Vector::ZERO, Vector::ZERO, link, link);
+        boxes.push(body);
+        previous = body;
+    }
+    boxes.pop();
+
+    let mut steps = 0;
+    while !boxes.iter().all(|body| is_sleeping(&world, *body)) {
+        world.step(DT);
+        steps += 1;
+        assert!(
+            steps < 64 * 60,
+            "the bridge did not fall asleep in a minute"
+        );
+    }
+}
+
+/// A heavy body drifting slower than `sleep_linear_velocity` falls asleep, and the kinetic energy
+/// it had is counted as dissipated.
+#[test]
+fn kinetic_energy_of_sleeping_body_is_dissipated() {
+    let mut world = world();
+    world.settings.gravity_vector = Vector::ZERO;
+    let body = world.add_body(
+        PhysicsTransform::IDENTITY,
+        RigidBody::new_sphere(1000.0, 1.0, Vector::new(40.0, 0.0, 0.0), Vector::ZERO),
+    );
+    let initial_energy = world.compute_energy();
+    assert!(initial_energy > 0.5);
+
+    for _ in 0..80 {
+        world.step(DT);
+    }
+
+    assert!(is_sleeping(&world, body));
+    assert_eq!(world.compute_energy(), 0.0);
+    let balance = world.compute_dissipated_energy() - initial_energy;
+    assert!(balance.abs() < 1e-6, "energy balance {balance}");
+}
+
+/// A force on any body of a sleeping island, or a kinematic body it hangs on starting to move,
+/// wakes up the whole island.
+#[test]
+fn disturbance_wakes_whole_island() {
+    let mut world = world();
+    let pivot = world.add_kinematic_body(PhysicsTransform::IDENTITY, Vector::ZERO, Vector::ZERO);
+    let chain = sleeping_chain(&mut world, pivot);
+    world.set_external_force(chain[1], Vector::new(1.0, 0.0, 0.0), Vector::ZERO);
+    world.step(DT);
+    assert!(chain.iter().all(|body| !is_sleeping(&world, *body)));
+
+    let mut world = self::world();
+    let pivot = world.add_kinematic_body(PhysicsTransform::IDENTITY, Vector::ZERO, Vector::ZERO);
+    let chain = sleeping_chain(&mut world, pivot);
+    world.set_kinematic_velocity(pivot, Vector::new(1.0, 0.0, 0.0), Vector::ZERO);
+    world.step(DT);
+    assert!(chain.iter().all(|body| !is_sleeping(&world, *body)));
+}
+
+/// A sleeping body is not moved by gravity or by the solver.
+#[test]
+fn sleeping_body_stays_put() {
+    let mut world = world();
+    let pivot = world.add_static_body(PhysicsTransform::IDENTITY);
+    let chain = sleeping_chain(&mut world, pivot);
+    let transforms = chain.map(|body| world.transform(body));
+
+    for _ in 0..64 * 5 {
+        world.step(DT);
+    }
+
+    assert_eq!(chain.map(|body| world.transform(body)), transforms);
+    assert!(chain.iter().all(|body| is_sleeping(&world, *body)));
+}
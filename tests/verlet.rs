@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    world::PhysicsWorld,
+};
+
+/// Default `FixedUpdate` timestep of Bevy
+const DT: Real = 1.0 / 64.0;
+
+/// A chain of light balls connected with stiff springs hanging on an anchor, with its lowest
+/// ball kicked sideways.
+/// Returns the largest distance of a ball from its initial position after two seconds, or
+/// `None` if the simulation blew up.
+fn simulate_chain(method: IntergrationMethod) -> Option<Real> {
+    const LENGTH: usize = 10;
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method,
+        gravity_vector: Vector::ZERO,
+        ..default()
+    });
+    let spring = Spring {
+        rest_length: 0.2,
+        spring_constant: 5e3,
+        ..default()
+    };
+    let mut previous = world.add_static_body(PhysicsTransform::IDENTITY);
+    let mut balls = Vec::new();
+    for i in 1..=LENGTH {
+        let pulse = if i == LENGTH {
+            Vector::new(0.05, 0.0, 0.0)
+        } else {
+            Vector::ZERO
+        };
+        let ball = world.add_body(
+            PhysicsTransform::from_xyz(0.0, -0.2 * i as Real, 0.0),
+            RigidBody::new_sphere(0.1, 0.05, pulse, Vector::ZERO),
+        );
+        world.add_spring(ball, previous, spring);
+        balls.push(ball);
+        previous = ball;
+    }
+    let initial: Vec<Vector> = balls
+        .iter()
+        .map(|ball| world.transform(*ball).translation)
+        .collect();
+
+    for _ in 0..128 {
+        world.step(DT);
+    }
+
+    let displacements: Vec<Real> = balls
+        .iter()
+        .zip(initial)
+        .map(|(ball, initial)| (world.transform(*ball).translation - initial).length())
+        .collect();
+    if displacements.iter().any(|d| !d.is_finite()) {
+        return None;
+    }
+    displacements.into_iter().reduce(Real::max)
+}
+
+/// Springs are so stiff that explicit Euler method blows up with the default timestep and
+/// substeps, while velocity Verlet method keeps the chain swinging.
+#[test]
+fn stiff_chain_stays_bounded() {
+    let explicit = simulate_chain(IntergrationMethod::EulerMethod);
+    assert!(
+        explicit.map_or(true, |displacement| displacement > 1e3),
+        "explicit Euler stayed bounded: {explicit:?}"
+    );
+
+    let verlet = simulate_chain(IntergrationMethod::VelocityVerlet).expect("Verlet blew up");
+    assert!(verlet < 1.0, "chain moved by {verlet}");
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use pdrust::{
+    body::{PhysicsTransform, RigidBody},
+    math::{consts::PI, to_f64, Real, Vector},
+    settings::{IntergrationMethod, SettingsResource},
+    springs::Spring,
+    world::PhysicsWorld,
+};
+
+/// A body hanging on a spring oscillates with period `2 * PI * sqrt(m / k)`
+/// around `m * g / k` below the rest length.
+#[test]
+fn spring_oscillator_matches_analytic_solution() {
+    let mass = 2.0;
+    let spring_constant = 50.0;
+    let mut world = PhysicsWorld::new(SettingsResource {
+        method: IntergrationMethod::RungeKutta4,
+        ..default()
+    });
+    let anchor = world.add_static_body(PhysicsTransform::IDENTITY);
+    let equilibrium = Vector::new(0.0, -1.0 - mass * 9.81 / spring_constant, 0.0);
+    let body = world.add_body(
+        PhysicsTransform::from_translation(equilibrium + Vector::new(0.0, -0.5, 0.0)),
+        RigidBody::new_sphere(mass, 0.1, Vector::ZERO, Vector::ZERO),
+    );
+    world.add_spring(
+        body,
+        anchor,
+        Spring {
+            spring_constant,
+            ..default()
+        },
+    );
+
+    let period = 2.0 * PI * (mass / spring_constant).sqrt();
+    let energy = world.compute_energy();
+    for _ in 0..1000 {
+        world.step(period / 1000.0);
+    }
+
+    let position = world.transform(body).translation;
+    assert!(
+        (position - equilibrium - Vector::new(0.0, -0.5, 0.0)).length() < 1e-3,
+        "body is at {position} after one period"
+    );
+    assert!((world.compute_energy() - energy).abs() < 1e-3 * energy.abs());
+    assert!((world.clock().elapsed_seconds() - to_f64(period)).abs() < 1e-4);
+    assert_eq!(world.transform(anchor), PhysicsTransform::IDENTITY);
+}
+
+/// Explicit Euler method makes energy of an oscillator grow, the projection keeps it constant.
+#[test]
+fn energy_projection_removes_euler_drift() {
+    let run = |energy_projection| {
+        let mut world = PhysicsWorld::new(SettingsResource {
+            method: IntergrationMethod::EulerMethod,
+            energy_projection,
+            ..default()
+        });
+        let anchor = world.add_static_body(PhysicsTransform::IDENTITY);
+        let body = world.add_body(
+            PhysicsTransform::from_xyz(0.0, -2.0, 0.0),
+            RigidBody::new_sphere(1.0, 0.1, Vector::new(1.0, 0.0, 0.0), Vector::ZERO),
+        );
+        world.add_spring(
+            body,
+            anchor,
+            Spring {
+                spring_constant: 50.0,
+                ..default()
+            },
+        );
+        let energy = world.compute_energy();
+        for _ in 0..600 {
+            world.step(1.0 / 60.0);
+        }
+        (
+            (world.compute_energy() - energy) / energy.abs(),
+            world.energy_projection().correction(),
+        )
+    };
+
+    let (drift, correction) = run(false);
+    assert!(drift > 1e-3, "energy drifted by {drift} only");
+    assert_eq!(correction, None);
+
+    let (drift, correction) = run(true);
+    assert!(drift.abs() < 1e-4, "energy drifted by {drift}");
+    assert!(correction.is_some());
+}
+
+/// Symplectic Euler method keeps energy of an oscillator bounded over a long run, while explicit
+/// Euler method keeps adding energy.
+#[test]
+fn semi_implicit_euler_keeps_energy_bounded() {
+    let largest_drift = |method| {
+        let mut world = PhysicsWorld::new(SettingsResource {
+            method,
+            ..default()
+        });
+        let anchor = world.add_static_body(PhysicsTransform::IDENTITY);
+        let body = world.add_body(
+            PhysicsTransform::from_xyz(0.0, -2.0, 0.0),
+            RigidBody::new_sphere(1.0, 0.1, Vector::new(1.0, 0.0, 0.0), Vector::ZERO),
+        );
+        world.add_spring(
+            body,
+            anchor,
+            Spring {
+                spring_constant: 50.0,
+                ..default()
+            },
+        );
+        let energy = world.compute_energy();
+        (0..3600)
+            .map(|_| {
+                world.step(1.0 / 60.0);
+                ((world.compute_energy() - energy) / energy.abs()).abs()
+            })
+            .fold(0.0, Real::max)
+    };
+
+    let explicit = largest_drift(IntergrationMethod::EulerMethod);
+    let semi_implicit = largest_drift(IntergrationMethod::SemiImplicitEuler);
+    assert!(explicit > 1e-1, "explicit Euler drifted by {explicit} only");
+    // The energy of the symplectic method oscillates by a couple of percent but does not grow
+    assert!(
+        semi_implicit < 5e-2,
+        "semi-implicit Euler drifted by {semi_implicit}"
+    );
+}
+
+/// Handles are checked, so a handle of one world can not silently refer to a body of another.
+#[test]
+#[should_panic(expected = "another world")]
+fn handle_of_another_world_is_rejected() {
+    let mut first = PhysicsWorld::default();
+    let mut second = PhysicsWorld::default();
+    let body = first.add_static_body(PhysicsTransform::IDENTITY);
+    second.add_static_body(PhysicsTransform::IDENTITY);
+    second.transform(body);
+}